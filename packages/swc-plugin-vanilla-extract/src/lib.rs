@@ -1,4 +1,3 @@
-use serde_json::Value;
 use swc_core::{
     ecma::{ast::Program, visit::*},
     plugin::{
@@ -7,45 +6,38 @@ use swc_core::{
     },
 };
 
-use swc_vanilla_extract_visitor::create_extract_visitor;
+use swc_vanilla_extract_visitor::{Config, VanillaExtractBuilder};
 
-#[plugin_transform]
-pub fn process(program: Program, metadata: TransformPluginProgramMetadata) -> Program {
-    let filename = metadata.get_context(&TransformPluginMetadataContextKind::Filename);
-    let filename = if let Some(filename) = filename.as_deref() {
-        filename
-    } else {
-        "unknown.js"
-    };
+// The lint fires on the exported functions `plugin_transform` generates next to the entry point,
+// which only the enclosing module can allow it for.
+#[allow(clippy::not_unsafe_ptr_arg_deref)]
+mod entry {
+    use super::*;
 
-    let cwd = metadata.get_context(&TransformPluginMetadataContextKind::Cwd);
-    let cwd = if let Some(cwd) = cwd.as_deref() {
-        cwd
-    } else {
-        "."
-    };
+    #[plugin_transform]
+    pub fn process(program: Program, metadata: TransformPluginProgramMetadata) -> Program {
+        transform(program, metadata)
+    }
+}
 
-    let config = metadata.get_transform_plugin_config();
-    let package_name = if let Some(config) = config {
-        let config: Value = serde_json::from_str(&config).expect("Config should be serializable");
+fn transform(program: Program, metadata: TransformPluginProgramMetadata) -> Program {
+    let filename = metadata.get_context(&TransformPluginMetadataContextKind::Filename);
+    let filename = filename.as_deref().unwrap_or("unknown.js");
 
-        let pkg_name = config["packageName"].as_str();
-        if let Some(pkg_name) = pkg_name {
-            pkg_name.to_string()
-        } else {
-            "swc-plugin-vanilla-extract".to_string()
-        }
-    } else {
-        "swc-plugin-vanilla-extract".to_string()
-    };
+    let cwd = metadata.get_context(&TransformPluginMetadataContextKind::Cwd);
+    let cwd = cwd.as_deref().unwrap_or(".");
 
-    let visitor = create_extract_visitor(
-        std::sync::Arc::new(metadata.source_map),
-        metadata.comments.as_ref(),
-        filename,
-        &package_name,
-        cwd,
-    );
+    let config =
+        match Config::from_plugin_options(metadata.get_transform_plugin_config().as_deref()) {
+            Some(config) => config,
+            None => return program,
+        };
 
-    program.fold_with(&mut as_folder(visitor))
+    program.fold_with(
+        &mut VanillaExtractBuilder::from(config).package_dir(cwd).build(
+            std::sync::Arc::new(metadata.source_map),
+            metadata.comments.as_ref(),
+            filename,
+        ),
+    )
 }
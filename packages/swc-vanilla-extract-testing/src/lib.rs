@@ -0,0 +1,436 @@
+//! Helpers to run the vanilla-extract transform over source strings, i.e to assert how a custom
+//! [Config] transforms real style files in tests of your own SWC pipeline.
+//!
+//! ```ignore
+//! let mut config = Config::default();
+//! config.package_dir = "/cwd".to_string();
+//!
+//! let code = transform_str(source, "src/button.css.ts", &config)?;
+//! ```
+//!
+//! Output is deterministic for given input, so it can be compared to snapshots as is.
+
+use std::{
+    io::Write,
+    ops::Range,
+    path::Path,
+    sync::{Arc, Mutex},
+};
+
+use anyhow::{bail, Context, Result};
+use swc_core::{
+    common::{
+        comments::SingleThreadedComments,
+        errors::{EmitterWriter, Handler, HandlerFlags, HANDLER},
+        sync::Lrc,
+        BytePos, FileLines, FileName, Globals, Loc, Mark, SourceMap, SourceMapper, Span,
+        SpanLinesError, SpanSnippetError, GLOBALS,
+    },
+    ecma::{
+        ast::Module,
+        codegen::{text_writer::JsWriter, Emitter},
+        parser::{parse_file_as_module, EsConfig, Syntax, TsConfig},
+        transforms::base::resolver,
+        visit::VisitMutWith,
+    },
+};
+use swc_vanilla_extract_visitor::{
+    Applicability, Config, Diagnostic, DiagnosticLog, StyleModuleReport, StyleModuleReportSlot,
+    VanillaExtractBuilder,
+};
+
+/// Result of running the transform over a file.
+#[derive(Debug, Clone)]
+pub struct TransformOutput {
+    /// Transformed code.
+    pub code: String,
+    /// Warnings and errors emitted while transforming, rendered without colors.
+    pub diagnostics: String,
+    /// Analysis of the file, `None` if it's not a style file.
+    pub report: Option<StyleModuleReport>,
+    /// Input code with the machine applicable fixes of the diagnostics applied, the input as is if
+    /// there are none.
+    pub fixed_code: String,
+    /// Input code with all fixes of the diagnostics applied, including the ones which may be
+    /// incorrect.
+    pub suggested_code: String,
+}
+
+/// Transforms given code as if it was the file at given filename, relative to the package
+/// directory of the config, and returns the transformed code.
+///
+/// Fails if the code can't be parsed, or if the transform emits an error.
+pub fn transform_str(code: &str, filename: &str, config: &Config) -> Result<String> {
+    Ok(transform(code, filename, config)?.code)
+}
+
+/// Transforms given code like [transform_str], and returns the diagnostics emitted and the report
+/// of the file along with the transformed code.
+pub fn transform(code: &str, filename: &str, config: &Config) -> Result<TransformOutput> {
+    let source_map: Lrc<SourceMap> = Default::default();
+    let diagnostics = DiagnosticBuffer::default();
+    let handler = Handler::with_emitter_and_flags(
+        Box::new(EmitterWriter::new(
+            Box::new(diagnostics.clone()),
+            Some(source_map.clone()),
+            false,
+            false,
+        )),
+        HandlerFlags {
+            can_emit_warnings: true,
+            ..Default::default()
+        },
+    );
+
+    let report = StyleModuleReportSlot::new();
+    let diagnostic_log = DiagnosticLog::new();
+    let mut config = config.clone();
+    config.report = Some(report.clone());
+    config.diagnostics = Some(diagnostic_log.clone());
+
+    let source_file = source_map.new_source_file(FileName::Real(filename.into()), code.to_string());
+    let transformed_code = GLOBALS.set(&Globals::new(), || {
+        HANDLER.set(&handler, || -> Result<String> {
+            let comments = SingleThreadedComments::default();
+            let syntax = get_syntax(filename);
+
+            let mut module = parse_file_as_module(
+                &source_file,
+                syntax,
+                Default::default(),
+                Some(&comments),
+                &mut vec![],
+            )
+            .map_err(|error| error.into_diagnostic(&handler).emit())
+            .ok()
+            .with_context(|| format!("Failed to parse {}\n{}", filename, diagnostics))?;
+
+            module.visit_mut_with(&mut resolver(Mark::new(), Mark::new(), syntax.typescript()));
+            module.visit_mut_with(&mut VanillaExtractBuilder::from(config).build_visitor(
+                Arc::new(SharedSourceMap(source_map.clone())),
+                comments.clone(),
+                filename,
+            ));
+
+            if handler.has_errors() {
+                bail!("Failed to transform {}\n{}", filename, diagnostics);
+            }
+
+            print(&source_map, &comments, &module)
+        })
+    })?;
+
+    let logged_diagnostics = diagnostic_log.take();
+    Ok(TransformOutput {
+        code: transformed_code,
+        diagnostics: diagnostics.to_string(),
+        report: report.take(),
+        fixed_code: apply_fixes(
+            code,
+            source_file.start_pos,
+            &logged_diagnostics,
+            Applicability::MachineApplicable,
+        ),
+        suggested_code: apply_fixes(
+            code,
+            source_file.start_pos,
+            &logged_diagnostics,
+            Applicability::MaybeIncorrect,
+        ),
+    })
+}
+
+/// Applies the fixes of given diagnostics to the code of a file starting at given position, the
+/// way tooling applying fixes would. Only machine applicable fixes are applied unless given
+/// `MaybeIncorrect`. Fixes overlapping a fix applied before are skipped.
+fn apply_fixes(
+    code: &str,
+    start_pos: BytePos,
+    diagnostics: &[Diagnostic],
+    applicability: Applicability,
+) -> String {
+    let mut edits: Vec<(Range<usize>, &str)> = vec![];
+    for fix in diagnostics
+        .iter()
+        .filter_map(|diagnostic| diagnostic.fix.as_ref())
+        .filter(|fix| {
+            applicability == Applicability::MaybeIncorrect
+                || fix.applicability == Applicability::MachineApplicable
+        })
+    {
+        let fix_edits = fix
+            .edits
+            .iter()
+            .map(|edit| {
+                let range = (edit.span.lo.0 - start_pos.0) as usize
+                    ..(edit.span.hi.0 - start_pos.0) as usize;
+                (range, edit.replacement.as_str())
+            })
+            .collect::<Vec<_>>();
+        let overlaps = fix_edits.iter().any(|(range, _)| {
+            edits
+                .iter()
+                .any(|(applied, _)| range.start < applied.end && applied.start < range.end)
+        });
+        if !overlaps {
+            edits.extend(fix_edits);
+        }
+    }
+
+    // Edits are applied from the end, so the ranges of the remaining ones stay valid. Insertions
+    // at the start of a replaced range go before the replacement.
+    edits.sort_by_key(|(range, _)| (range.start, range.end));
+    let mut fixed_code = code.to_string();
+    for (range, replacement) in edits.into_iter().rev() {
+        fixed_code.replace_range(range, replacement);
+    }
+    fixed_code
+}
+
+/// Picks the syntax to parse given file with from its extension, ignoring bundler query strings.
+fn get_syntax(filename: &str) -> Syntax {
+    let path = filename.split(['?', '#']).next().unwrap_or(filename);
+
+    match Path::new(path).extension().and_then(|ext| ext.to_str()) {
+        Some("ts" | "mts" | "cts") => Syntax::Typescript(Default::default()),
+        Some("tsx") => Syntax::Typescript(TsConfig {
+            tsx: true,
+            ..Default::default()
+        }),
+        _ => Syntax::Es(EsConfig {
+            jsx: true,
+            ..Default::default()
+        }),
+    }
+}
+
+fn print(
+    source_map: &Lrc<SourceMap>,
+    comments: &SingleThreadedComments,
+    module: &Module,
+) -> Result<String> {
+    let mut buf = vec![];
+    {
+        let mut emitter = Emitter {
+            cfg: Default::default(),
+            cm: source_map.clone(),
+            comments: Some(comments),
+            wr: JsWriter::new(source_map.clone(), "\n", &mut buf, None),
+        };
+        emitter.emit_module(module)?;
+    }
+
+    Ok(String::from_utf8(buf)?)
+}
+
+/// Source map shared with the visitor, which takes it behind an `Arc` while the parser and the
+/// emitter use `Lrc`, an `Rc` unless swc_common's concurrent mode is enabled. Wrap the source map
+/// of a transform test in it to build the visitor, i.e `Arc::new(SharedSourceMap(t.cm.clone()))`.
+pub struct SharedSourceMap(pub Lrc<SourceMap>);
+
+impl SourceMapper for SharedSourceMap {
+    fn lookup_char_pos(&self, pos: BytePos) -> Loc {
+        self.0.lookup_char_pos(pos)
+    }
+
+    fn span_to_lines(&self, sp: Span) -> Result<FileLines, Box<SpanLinesError>> {
+        self.0.span_to_lines(sp)
+    }
+
+    fn span_to_string(&self, sp: Span) -> String {
+        self.0.span_to_string(sp)
+    }
+
+    fn span_to_filename(&self, sp: Span) -> FileName {
+        self.0.span_to_filename(sp)
+    }
+
+    fn merge_spans(&self, sp_lhs: Span, sp_rhs: Span) -> Option<Span> {
+        SourceMapper::merge_spans(&*self.0, sp_lhs, sp_rhs)
+    }
+
+    fn call_span_if_macro(&self, sp: Span) -> Span {
+        self.0.call_span_if_macro(sp)
+    }
+
+    fn doctest_offset_line(&self, line: usize) -> usize {
+        self.0.doctest_offset_line(line)
+    }
+
+    fn span_to_snippet(&self, sp: Span) -> Result<String, Box<SpanSnippetError>> {
+        self.0.span_to_snippet(sp)
+    }
+}
+
+/// Buffer the handler renders diagnostics into.
+#[derive(Clone, Default)]
+struct DiagnosticBuffer(Arc<Mutex<Vec<u8>>>);
+
+impl Write for DiagnosticBuffer {
+    fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+        self.0
+            .lock()
+            .expect("Diagnostic buffer should not be poisoned")
+            .write(buf)
+    }
+
+    fn flush(&mut self) -> std::io::Result<()> {
+        Ok(())
+    }
+}
+
+impl std::fmt::Display for DiagnosticBuffer {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let buf = self
+            .0
+            .lock()
+            .expect("Diagnostic buffer should not be poisoned");
+        f.write_str(&String::from_utf8_lossy(&buf))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use pretty_assertions::assert_eq;
+
+    use swc_vanilla_extract_visitor::{Rule, Severity};
+
+    use super::*;
+
+    fn config() -> Config {
+        let mut config = Config::default();
+        config.package_dir = "/cwd".to_string();
+        config
+    }
+
+    #[test]
+    fn transforms_style_file() {
+        let code = transform_str(
+            "import { style } from '@vanilla-extract/css';\nexport const root: string = style({ zIndex: 1 });\n",
+            "src/button.css.ts",
+            &config(),
+        )
+        .unwrap();
+
+        assert_eq!(
+            code,
+            r#"/*@vanilla-extract/file-scope*/ import * as __vanilla_filescope__ from "@vanilla-extract/css/fileScope";
+__vanilla_filescope__.setFileScope("/cwd/src/button.css.ts", "swc-plugin-vanilla-extract");
+import { style } from '@vanilla-extract/css';
+export const root: string = style({
+    zIndex: 1
+}, "root");
+__vanilla_filescope__.endFileScope();
+"#
+        );
+    }
+
+    #[test]
+    fn reports_style_file() {
+        let output = transform(
+            "import { style } from '@vanilla-extract/css';\nexport const root = style({});\n",
+            "src/button.css.ts",
+            &config(),
+        )
+        .unwrap();
+        let report = output.report.unwrap();
+
+        assert_eq!(output.diagnostics, "");
+        assert_eq!(report.file_path, "/cwd/src/button.css.ts");
+        assert_eq!(report.styles[0].debug_id.as_deref(), Some("root"));
+
+        let output = transform("const root = {};\n", "src/button.tsx", &config()).unwrap();
+
+        assert_eq!(output.code, "const root = {};\n");
+        assert!(output.report.is_none());
+    }
+
+    #[test]
+    fn captures_warnings() {
+        let mut config = config();
+        config.rules.set(Rule::DuplicateDebugIds, Severity::Warning);
+
+        let output = transform(
+            "import { style } from '@vanilla-extract/css';\nconst root = [style({}), style({})];\n",
+            "src/button.css.ts",
+            &config,
+        )
+        .unwrap();
+
+        assert!(output.code.contains(r#""root_2""#));
+        assert!(output
+            .diagnostics
+            .contains("Debug id `root` is already used in this file"));
+    }
+
+    #[test]
+    fn applies_fixes() {
+        let mut config = config();
+        config.rules.set(Rule::DuplicateDebugIds, Severity::Warning);
+
+        let output = transform(
+            "import { style } from '@vanilla-extract/css';\n\
+             export const root = [style({ colr: 'red' }), style({})];\n\
+             const lazy = () => style({ zIndex: 1 });\n",
+            "src/button.css.ts",
+            &config,
+        )
+        .unwrap();
+
+        assert_eq!(
+            output.fixed_code,
+            "import { style } from '@vanilla-extract/css';\n\
+             export const root = [style({ colr: 'red' }), style({}, \"root_2\")];\n\
+             const lazy = () => style({ zIndex: 1 });\n"
+        );
+        assert_eq!(
+            output.suggested_code,
+            "import { style } from '@vanilla-extract/css';\n\
+             export const root = [style({ color: 'red' }), style({}, \"root_2\")];\n\
+             const hoistedStyle = style({ zIndex: 1 });\n\
+             const lazy = () => hoistedStyle;\n"
+        );
+    }
+
+    #[test]
+    fn hoists_before_leading_comments() {
+        let output = transform(
+            "import { style } from '@vanilla-extract/css';\n\
+             /** Creates the style lazily. */\n\
+             // @ve-debug-id card\n\
+             const lazy = () => style({ zIndex: 1 });\n",
+            "src/button.css.ts",
+            &config(),
+        )
+        .unwrap();
+
+        assert_eq!(
+            output.suggested_code,
+            "import { style } from '@vanilla-extract/css';\n\
+             const hoistedStyle = style({ zIndex: 1 });\n\
+             /** Creates the style lazily. */\n\
+             // @ve-debug-id card\n\
+             const lazy = () => hoistedStyle;\n"
+        );
+    }
+
+    #[test]
+    fn does_not_hoist_before_later_enums() {
+        let code = "import { style } from '@vanilla-extract/css';\n\
+                    const lazy = () => style({ color: Theme.brand });\n\
+                    enum Theme { brand = 'red' }\n";
+        let output = transform(code, "src/button.css.ts", &config()).unwrap();
+
+        assert!(output.diagnostics.contains("VE002"));
+        assert_eq!(output.suggested_code, code);
+    }
+
+    #[test]
+    fn fails_on_parse_error() {
+        let error = transform_str("const = ;", "src/button.css.ts", &config()).unwrap_err();
+
+        assert!(error
+            .to_string()
+            .starts_with("Failed to parse src/button.css.ts"));
+    }
+}
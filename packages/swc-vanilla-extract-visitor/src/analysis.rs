@@ -0,0 +1,252 @@
+//! Analysis primitives the transform is built on, for SWC plugins and tools which need to
+//! understand vanilla-extract style files without transforming them, i.e resolving a callee to the
+//! vanilla-extract export it refers to.
+//!
+//! Items in this module follow semver on their own: changes to the transform's output or options
+//! do not change them, and they are only changed in a breaking way with a major version bump.
+
+use std::collections::HashMap;
+
+use swc_core::{
+    common::Span,
+    ecma::{
+        ast::{CallExpr, Id, Ident, ModuleItem},
+        atoms::JsWord,
+        visit::VisitWith,
+    },
+};
+
+use crate::{
+    constants::{
+        CSS_FILE_FILTER_REGEX, DEBUGGABLE_FUNCTION_CONFIG, PACKAGE_IDENTIFIERS, STYLE_FUNCTIONS,
+    },
+    file_path::strip_query,
+    get_relavant_call::{get_debug_id_position, get_relavant_call},
+    import_collect_visitor::{self, ImportCollectVisitor},
+    wrapper_collect_visitor::WrapperCollectVisitor,
+};
+
+/// Returns true if given filename is a vanilla-extract style file, i.e `button.css.ts`. Bundler
+/// query strings are ignored.
+pub fn is_style_file(filename: &str) -> bool {
+    CSS_FILE_FILTER_REGEX.is_match(strip_query(filename))
+}
+
+/// Returns true if given module specifier is a vanilla-extract package exporting style functions.
+pub fn is_vanilla_extract_package(src: &str) -> bool {
+    PACKAGE_IDENTIFIERS.contains(src)
+}
+
+/// Returns true if given module specifier resolves to `@vanilla-extract/css/fileScope`, which
+/// means the importing file has already been compiled.
+pub fn is_file_scope_source(src: &str) -> bool {
+    import_collect_visitor::is_file_scope_source(src)
+}
+
+/// Returns true if given name is a style function exported by vanilla-extract, i.e `style`.
+pub fn is_style_function(name: &str) -> bool {
+    STYLE_FUNCTIONS.contains(&name)
+}
+
+/// Returns the number of arguments of given style function including the trailing debug id, if
+/// it accepts one, i.e `2` for `style(rule, debugId)`.
+pub fn debuggable_arity(name: &str) -> Option<usize> {
+    DEBUGGABLE_FUNCTION_CONFIG.get(name).copied()
+}
+
+/// Imports, exports and local wrapper functions of a module, as far as vanilla-extract is
+/// concerned.
+#[derive(Debug, Clone, Default)]
+pub struct ModuleAnalysis {
+    pub(crate) is_compiled: bool,
+    pub(crate) namespace_import: Option<Ident>,
+    pub(crate) import_identifiers: HashMap<Ident, String>,
+    pub(crate) exports: HashMap<JsWord, String>,
+    pub(crate) wrappers: HashMap<Id, usize>,
+    pub(crate) imports: Vec<(String, Span)>,
+}
+
+impl ModuleAnalysis {
+    pub fn analyze(items: &[ModuleItem]) -> Self {
+        let mut import_collect_visitor = ImportCollectVisitor::new();
+        for item in items {
+            item.visit_children_with(&mut import_collect_visitor);
+        }
+
+        let mut wrapper_collect_visitor = WrapperCollectVisitor::new(
+            &import_collect_visitor.namespace_import,
+            &import_collect_visitor.import_identifiers,
+        );
+        // Wrappers may call other wrappers declared later in the file, collect until no new
+        // wrapper is found.
+        loop {
+            let wrapper_count = wrapper_collect_visitor.wrappers.len();
+            for item in items {
+                item.visit_with(&mut wrapper_collect_visitor);
+            }
+            if wrapper_collect_visitor.wrappers.len() == wrapper_count {
+                break;
+            }
+        }
+        let wrappers = wrapper_collect_visitor.wrappers;
+
+        ModuleAnalysis {
+            is_compiled: import_collect_visitor.is_compiled,
+            namespace_import: import_collect_visitor.namespace_import,
+            import_identifiers: import_collect_visitor.import_identifiers,
+            exports: import_collect_visitor.exports,
+            wrappers,
+            imports: import_collect_visitor.imports,
+        }
+    }
+
+    /// Returns true if the module calls `setFileScope` imported or required from the file scope
+    /// API, meaning it has already been compiled by vanilla-extract.
+    pub fn is_compiled(&self) -> bool {
+        self.is_compiled
+    }
+
+    /// Returns the local binding of `import * as css from '@vanilla-extract/css'`, if any.
+    pub fn namespace_import(&self) -> Option<&Ident> {
+        self.namespace_import.as_ref()
+    }
+
+    /// Returns the style functions imported by name, with the vanilla-extract export each local
+    /// binding refers to.
+    pub fn named_imports(&self) -> impl Iterator<Item = (&Ident, &str)> {
+        self.import_identifiers
+            .iter()
+            .map(|(local, export)| (local, export.as_str()))
+    }
+
+    /// Resolves the callee of given call to the vanilla-extract style function it refers to, i.e
+    /// `style` for `css.style()` or `s()` after `import { style as s }`.
+    pub fn resolve_callee(&self, call_expr: &CallExpr) -> Option<String> {
+        get_relavant_call(call_expr, &self.namespace_import, &self.import_identifiers)
+    }
+
+    /// Returns the name top level binding is exported as, `default` for default exports.
+    pub fn export_name(&self, local_name: &str) -> Option<&str> {
+        self.exports
+            .get(&JsWord::from(local_name))
+            .map(|export| export.as_str())
+    }
+
+    /// Returns the position of the parameter given local function forwards as the debug id of a
+    /// style function, if it's a wrapper.
+    pub fn wrapper_debug_id_position(&self, function: &Ident) -> Option<usize> {
+        self.wrappers.get(&function.to_id()).copied()
+    }
+
+    /// Returns the argument position a debug id would be injected at, if given call is a call to a
+    /// style function or a local wrapper which does not pass a debug id yet.
+    pub fn debug_id_position(&self, call_expr: &CallExpr) -> Option<usize> {
+        get_debug_id_position(
+            call_expr,
+            &self.namespace_import,
+            &self.import_identifiers,
+            &self.wrappers,
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use pretty_assertions::assert_eq;
+    use swc_core::{common::DUMMY_SP, ecma::ast::Expr, quote};
+
+    use super::*;
+
+    fn call(expr: Expr) -> CallExpr {
+        match expr {
+            Expr::Call(call_expr) => call_expr,
+            _ => unreachable!("Expression should be a call"),
+        }
+    }
+
+    #[test]
+    fn resolves_callee() {
+        let items = vec![
+            quote!("import { style as s, globalStyle } from '@vanilla-extract/css';" as ModuleItem),
+            quote!("const card = (rule, id) => s(rule, id);" as ModuleItem),
+            quote!("export { card as panel };" as ModuleItem),
+        ];
+        let analysis = ModuleAnalysis::analyze(&items);
+
+        assert!(!analysis.is_compiled());
+        assert_eq!(
+            analysis.resolve_callee(&call(quote!("s({})" as Expr))),
+            Some("style".to_string())
+        );
+        assert_eq!(
+            analysis.resolve_callee(&call(quote!("style({})" as Expr))),
+            None
+        );
+        assert_eq!(
+            analysis.debug_id_position(&call(quote!("s({})" as Expr))),
+            Some(1)
+        );
+        assert_eq!(
+            analysis.debug_id_position(&call(quote!("s({}, 'id')" as Expr))),
+            None
+        );
+        assert_eq!(
+            analysis.debug_id_position(&call(quote!("globalStyle('a', {})" as Expr))),
+            None
+        );
+        assert_eq!(
+            analysis.wrapper_debug_id_position(&Ident::new("card".into(), DUMMY_SP)),
+            Some(1)
+        );
+        assert_eq!(
+            analysis.debug_id_position(&call(quote!("card()" as Expr))),
+            Some(1)
+        );
+        assert_eq!(analysis.export_name("card"), Some("panel"));
+    }
+
+    #[test]
+    fn resolves_namespace_import() {
+        let items = vec![quote!(
+            "import * as css from '@vanilla-extract/css';" as ModuleItem
+        )];
+        let analysis = ModuleAnalysis::analyze(&items);
+
+        assert_eq!(
+            analysis.namespace_import().map(|ident| &*ident.sym),
+            Some("css")
+        );
+        assert_eq!(
+            analysis.resolve_callee(&call(quote!("css.recipe({})" as Expr))),
+            Some("recipe".to_string())
+        );
+        assert_eq!(
+            analysis.resolve_callee(&call(quote!("css.unknown({})" as Expr))),
+            None
+        );
+    }
+
+    #[test]
+    fn detects_compiled_module() {
+        let mut items = vec![quote!(
+            "import * as fileScope from '@vanilla-extract/css/fileScope';" as ModuleItem
+        )];
+        assert!(!ModuleAnalysis::analyze(&items).is_compiled());
+
+        items.push(quote!(
+            "fileScope.setFileScope('src/button.css.ts', 'app');" as ModuleItem
+        ));
+        assert!(ModuleAnalysis::analyze(&items).is_compiled());
+    }
+
+    #[test]
+    fn classifies_names() {
+        assert!(is_style_file("src/button.css.ts?used"));
+        assert!(!is_style_file("src/button.ts"));
+        assert!(is_vanilla_extract_package("@vanilla-extract/recipes"));
+        assert!(is_file_scope_source("@vanilla-extract/css/fileScope"));
+        assert!(is_style_function("globalStyle"));
+        assert_eq!(debuggable_arity("style"), Some(2));
+        assert_eq!(debuggable_arity("globalStyle"), None);
+    }
+}
@@ -0,0 +1,442 @@
+use std::sync::Arc;
+
+use serde_json::{Map, Value};
+use swc_core::{
+    common::{comments::Comments, errors::HANDLER, SourceMapper},
+    ecma::visit::{as_folder, Fold, VisitMut},
+};
+
+use crate::{
+    debug_id_sanitizer::MIN_MAX_LENGTH, DebugIdSanitizer, DebugIdTemplate, DiagnosticLog,
+    HmrRuntime, ImportFilter, PackageMap, Rule, RuleConfig, Severity, StyleModuleReportSlot,
+    VanillaExtractVisitor,
+};
+
+/// Options of the vanilla-extract transform. New options are added as fields with a default, so
+/// construct it with `Config::default()` and set the fields needed.
+#[derive(Debug, Clone)]
+#[non_exhaustive]
+pub struct Config {
+    /// Package name used for the file scope.
+    pub package_name: String,
+    /// Directory of the package, relative filenames are resolved against it.
+    pub package_dir: String,
+    /// Map of path patterns to package names. Files matching one of the patterns use the mapped
+    /// package name for the file scope instead of the default package name.
+    pub package_map: PackageMap,
+    /// Template to build debug ids from, i.e `[fileStem]_[name]`.
+    pub debug_id_template: Option<DebugIdTemplate>,
+    /// Sanitizer applied to debug ids, or `None` to inject them verbatim. Off by default, as it
+    /// changes the class names of existing debug ids.
+    pub debug_id_sanitizer: Option<DebugIdSanitizer>,
+    /// Handles hot updates of the module with given dev server's HMR API, clearing styles of the
+    /// previous version of the module when it's disposed. Updates propagate to importers.
+    pub hmr: Option<HmrRuntime>,
+    /// Makes the module accept its own hot updates in `hmr` mode, instead of propagating them to
+    /// importers. Off by default, as importers keep the class names of the previous version.
+    pub hmr_accept: bool,
+    /// Slot to store the analysis of the transformed file in, read it after the pass.
+    pub report: Option<StyleModuleReportSlot>,
+    /// Module specifiers style files may import, denying `.tsx` imports by default.
+    pub import_filter: ImportFilter,
+    /// Severity of each rule, rules not configured use their default severity.
+    pub rules: RuleConfig,
+    /// Reports warnings as errors, failing the transform.
+    pub strict: bool,
+    /// Log to record the diagnostics reported in, read it after the pass.
+    pub diagnostics: Option<DiagnosticLog>,
+}
+
+impl Config {
+    /// Returns the severity diagnostics of given rule are reported with, warnings being errors in
+    /// strict mode.
+    pub fn severity(&self, rule: Rule) -> Severity {
+        match self.rules.get(rule) {
+            Severity::Warning if self.strict => Severity::Error,
+            severity => severity,
+        }
+    }
+}
+
+impl Default for Config {
+    fn default() -> Self {
+        Config {
+            package_name: "swc-plugin-vanilla-extract".to_string(),
+            package_dir: ".".to_string(),
+            package_map: Default::default(),
+            debug_id_template: None,
+            debug_id_sanitizer: None,
+            hmr: None,
+            hmr_accept: false,
+            report: None,
+            import_filter: Default::default(),
+            rules: Default::default(),
+            strict: false,
+            diagnostics: None,
+        }
+    }
+}
+
+impl Config {
+    /// Parses the options of the plugin, i.e `{ "packageName": "@acme/button", "hmr": "vite" }`.
+    /// `null` gives the default config. The package dir is not an option, set it from the working
+    /// directory of the host. Unknown keys are ignored.
+    pub fn from_json(options: &str) -> Result<Self, String> {
+        let options = match serde_json::from_str(options)
+            .map_err(|err| format!("Options should be valid JSON: {}", err))?
+        {
+            Value::Object(options) => options,
+            Value::Null => return Ok(Default::default()),
+            _ => return Err("Options should be an object".to_string()),
+        };
+
+        let mut config = Config::default();
+
+        if let Some(package_name) = options.get("packageName") {
+            config.package_name = as_str(package_name, "packageName")?.to_string();
+        }
+
+        if let Some(package_map) = options.get("packageMap") {
+            config.package_map = as_object(package_map, "packageMap")?
+                .iter()
+                .map(|(pattern, package_name)| {
+                    let package_name = package_name
+                        .as_str()
+                        .ok_or_else(|| "`packageMap` values should be package names".to_string())?;
+                    Ok((pattern, package_name))
+                })
+                .collect::<Result<PackageMap, String>>()?;
+        }
+
+        if let Some(debug_id_template) = options.get("debugIdTemplate") {
+            config.debug_id_template = Some(DebugIdTemplate::new(as_str(
+                debug_id_template,
+                "debugIdTemplate",
+            )?));
+        }
+
+        match options.get("debugIdSanitize") {
+            None | Some(Value::Bool(false)) => {}
+            Some(Value::Bool(true)) => config.debug_id_sanitizer = Some(Default::default()),
+            Some(Value::Object(sanitize)) => {
+                config.debug_id_sanitizer = Some(match sanitize.get("maxLength") {
+                    Some(max_length) => DebugIdSanitizer::new(
+                        max_length
+                            .as_u64()
+                            .map(|max_length| max_length as usize)
+                            .filter(|max_length| *max_length >= MIN_MAX_LENGTH)
+                            .ok_or_else(|| {
+                                format!(
+                                    "`debugIdSanitize.maxLength` should be an integer of at least {}",
+                                    MIN_MAX_LENGTH
+                                )
+                            })?,
+                    ),
+                    None => Default::default(),
+                });
+            }
+            Some(_) => {
+                return Err("`debugIdSanitize` should be a boolean or an object".to_string());
+            }
+        }
+
+        // Shorthand for `duplicateDebugIds: "warning"`, which predates rule severities
+        if let Some(warn_duplicate_debug_ids) = options.get("warnDuplicateDebugIds") {
+            if as_bool(warn_duplicate_debug_ids, "warnDuplicateDebugIds")? {
+                config.rules.set(Rule::DuplicateDebugIds, Severity::Warning);
+            }
+        }
+
+        if let Some(hmr) = options.get("hmr") {
+            config.hmr = Some(as_str(hmr, "hmr")?.parse()?);
+        }
+
+        if let Some(hmr_accept) = options.get("hmrAccept") {
+            config.hmr_accept = as_bool(hmr_accept, "hmrAccept")?;
+        }
+
+        // A deny list replaces the default one, an allow list extends the filter either way
+        if let Some(patterns) = options.get("importDenyList") {
+            config.import_filter = ImportFilter::new();
+            for pattern in as_str_array(patterns, "importDenyList")? {
+                config.import_filter.deny(pattern);
+            }
+        }
+        if let Some(patterns) = options.get("importAllowList") {
+            for pattern in as_str_array(patterns, "importAllowList")? {
+                config.import_filter.allow(pattern);
+            }
+        }
+
+        // Severities are set by rule name, i.e `lazyStyleCalls: "error"`, or by name or code in
+        // `rules`
+        for rule in Rule::ALL {
+            if let Some(severity) = options.get(rule.name()) {
+                config
+                    .rules
+                    .set(rule, as_str(severity, rule.name())?.parse()?);
+            }
+        }
+        if let Some(rules) = options.get("rules") {
+            for (rule, severity) in as_object(rules, "rules")? {
+                let severity = severity
+                    .as_str()
+                    .ok_or_else(|| "`rules` values should be severities".to_string())?;
+                config.rules.set(rule.parse()?, severity.parse()?);
+            }
+        }
+
+        if let Some(strict) = options.get("strict") {
+            config.strict = as_bool(strict, "strict")?;
+        }
+
+        Ok(config)
+    }
+
+    /// Parses the options given to an entry point of the transform, defaulting when there are
+    /// none. Invalid options are reported as an error of the transform through `HANDLER`, and
+    /// `None` is returned for the entry point to leave the program untransformed.
+    pub fn from_plugin_options(options: Option<&str>) -> Option<Self> {
+        match options.map(Config::from_json).transpose() {
+            Ok(config) => Some(config.unwrap_or_default()),
+            Err(err) => {
+                HANDLER.with(|handler| {
+                    handler.err(&format!(
+                        "Invalid swc-plugin-vanilla-extract options: {}",
+                        err
+                    ))
+                });
+                None
+            }
+        }
+    }
+}
+
+fn as_str<'v>(value: &'v Value, key: &str) -> Result<&'v str, String> {
+    value
+        .as_str()
+        .ok_or_else(|| format!("`{}` should be a string", key))
+}
+
+fn as_bool(value: &Value, key: &str) -> Result<bool, String> {
+    value
+        .as_bool()
+        .ok_or_else(|| format!("`{}` should be a boolean", key))
+}
+
+fn as_object<'v>(value: &'v Value, key: &str) -> Result<&'v Map<String, Value>, String> {
+    value
+        .as_object()
+        .ok_or_else(|| format!("`{}` should be an object", key))
+}
+
+fn as_str_array<'v>(value: &'v Value, key: &str) -> Result<Vec<&'v str>, String> {
+    value
+        .as_array()
+        .ok_or_else(|| format!("`{}` should be an array", key))?
+        .iter()
+        .map(|pattern| {
+            pattern
+                .as_str()
+                .ok_or_else(|| format!("`{}` should contain patterns", key))
+        })
+        .collect()
+}
+
+/// Entry point to create the vanilla-extract transform pass.
+///
+/// ```ignore
+/// let pass = VanillaExtract::builder()
+///     .package_name("@acme/button")
+///     .package_dir(cwd)
+///     .build(source_map, comments, filename);
+/// ```
+pub struct VanillaExtract;
+
+impl VanillaExtract {
+    pub fn builder() -> VanillaExtractBuilder {
+        Default::default()
+    }
+}
+
+/// Builder for the vanilla-extract transform pass. See [Config] for the options.
+#[derive(Debug, Clone, Default)]
+pub struct VanillaExtractBuilder {
+    config: Config,
+}
+
+impl From<Config> for VanillaExtractBuilder {
+    fn from(config: Config) -> Self {
+        VanillaExtractBuilder { config }
+    }
+}
+
+impl VanillaExtractBuilder {
+    pub fn package_name(mut self, package_name: &str) -> Self {
+        self.config.package_name = package_name.to_string();
+        self
+    }
+
+    pub fn package_dir(mut self, package_dir: &str) -> Self {
+        self.config.package_dir = package_dir.to_string();
+        self
+    }
+
+    pub fn package_map(mut self, package_map: PackageMap) -> Self {
+        self.config.package_map = package_map;
+        self
+    }
+
+    pub fn debug_id_template(mut self, debug_id_template: DebugIdTemplate) -> Self {
+        self.config.debug_id_template = Some(debug_id_template);
+        self
+    }
+
+    pub fn debug_id_sanitizer(mut self, debug_id_sanitizer: Option<DebugIdSanitizer>) -> Self {
+        self.config.debug_id_sanitizer = debug_id_sanitizer;
+        self
+    }
+
+    pub fn hmr(mut self, hmr: HmrRuntime) -> Self {
+        self.config.hmr = Some(hmr);
+        self
+    }
+
+    pub fn hmr_accept(mut self, hmr_accept: bool) -> Self {
+        self.config.hmr_accept = hmr_accept;
+        self
+    }
+
+    pub fn report(mut self, report: StyleModuleReportSlot) -> Self {
+        self.config.report = Some(report);
+        self
+    }
+
+    pub fn import_filter(mut self, import_filter: ImportFilter) -> Self {
+        self.config.import_filter = import_filter;
+        self
+    }
+
+    pub fn rule(mut self, rule: Rule, severity: Severity) -> Self {
+        self.config.rules.set(rule, severity);
+        self
+    }
+
+    pub fn strict(mut self, strict: bool) -> Self {
+        self.config.strict = strict;
+        self
+    }
+
+    pub fn diagnostics(mut self, diagnostics: DiagnosticLog) -> Self {
+        self.config.diagnostics = Some(diagnostics);
+        self
+    }
+
+    pub fn config(&self) -> &Config {
+        &self.config
+    }
+
+    /// Creates the visitor transforming given file.
+    pub fn build_visitor<C: Comments, S: SourceMapper>(
+        self,
+        source_map: Arc<S>,
+        comments: C,
+        filename: &str,
+    ) -> VanillaExtractVisitor<C, S> {
+        VanillaExtractVisitor::from_config(source_map, comments, filename, self.config)
+    }
+
+    /// Creates the pass transforming given file, to run as a custom pass or fold a `Program` with.
+    pub fn build<C: Comments, S: SourceMapper>(
+        self,
+        source_map: Arc<S>,
+        comments: C,
+        filename: &str,
+    ) -> impl Fold + VisitMut {
+        as_folder(self.build_visitor(source_map, comments, filename))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use pretty_assertions::assert_eq;
+
+    use super::*;
+
+    #[test]
+    fn parses_options() {
+        let config = Config::from_json(
+            r#"{
+                "packageName": "@acme/button",
+                "packageMap": { "packages/card": "@acme/card" },
+                "hmr": "vite",
+                "hmrAccept": true,
+                "debugIdSanitize": true,
+                "importDenyList": ["lodash"],
+                "lazyStyleCalls": "error",
+                "rules": { "VE007": "off" },
+                "strict": true
+            }"#,
+        )
+        .unwrap();
+
+        assert_eq!(config.package_name, "@acme/button");
+        assert_eq!(
+            config.package_map.resolve("packages/card/card.css.ts"),
+            Some("@acme/card")
+        );
+        assert_eq!(config.hmr, Some(HmrRuntime::Vite));
+        assert!(config.hmr_accept);
+        assert!(config.debug_id_sanitizer.is_some());
+        assert_eq!(config.import_filter.denied_by("lodash"), Some("lodash"));
+        assert_eq!(config.import_filter.denied_by("./button.tsx"), None);
+        assert_eq!(config.rules.get(Rule::LazyStyleCalls), Severity::Error);
+        assert_eq!(config.rules.get(Rule::UnusedStyles), Severity::Off);
+        assert!(config.strict);
+    }
+
+    #[test]
+    fn defaults_null_options() {
+        let config = Config::from_json("null").unwrap();
+
+        assert_eq!(config.package_name, "swc-plugin-vanilla-extract");
+        assert_eq!(config.hmr, None);
+        assert!(!config.hmr_accept);
+        assert!(config.debug_id_sanitizer.is_none());
+    }
+
+    #[test]
+    fn rejects_invalid_options() {
+        let error = |options: &str| Config::from_json(options).err();
+
+        assert_eq!(
+            error(r#"{ "packageMap": { "packages/card": 1 } }"#),
+            Some("`packageMap` values should be package names".to_string())
+        );
+        assert_eq!(
+            error(r#"{ "hmr": "parcel" }"#),
+            Some("Unsupported hmr runtime: parcel".to_string())
+        );
+        assert_eq!(
+            error(r#"{ "importAllowList": [1] }"#),
+            Some("`importAllowList` should contain patterns".to_string())
+        );
+        assert_eq!(
+            error(r#"{ "rules": { "VE999": "error" } }"#),
+            Some("Unknown rule: VE999".to_string())
+        );
+        assert_eq!(
+            error(r#"{ "unusedStyles": "fatal" }"#),
+            Some("Unsupported severity: fatal".to_string())
+        );
+        assert_eq!(
+            error(r#"{ "debugIdSanitize": { "maxLength": 0 } }"#),
+            Some("`debugIdSanitize.maxLength` should be an integer of at least 8".to_string())
+        );
+        assert_eq!(
+            error(r#"{ "debugIdSanitize": { "maxLength": 7 } }"#),
+            Some("`debugIdSanitize.maxLength` should be an integer of at least 8".to_string())
+        );
+        assert_eq!(error("[]"), Some("Options should be an object".to_string()));
+    }
+}
@@ -7,6 +7,17 @@ use swc_core::{common::DUMMY_SP, ecma::ast::Ident};
 pub static FILE_SCOPE_IMPORT_NAME: Lazy<Ident> =
     Lazy::new(|| Ident::new("__vanilla_filescope__".into(), DUMMY_SP));
 pub static FILE_SCOPE_PACKAGE_IDENTIFIER: &str = "@vanilla-extract/css/fileScope";
+/// Vite's dependency optimizer rewrites `@vanilla-extract/css/fileScope` into a prebundled chunk
+/// named after the flattened specifier.
+pub static FILE_SCOPE_OPTIMIZED_DEP_IDENTIFIER: &str = "@vanilla-extract_css_fileScope";
+/// Marker comment emitted with the file scope import, to recognize files already transformed by
+/// this plugin even after the import itself has been rewritten by other transforms.
+pub static FILE_SCOPE_MARKER_COMMENT: &str = "@vanilla-extract/file-scope";
+
+pub static DEBUG_ID_DIRECTIVE: &str = "@ve-debug-id";
+pub static IGNORE_DIRECTIVE: &str = "@ve-ignore";
+pub static DISABLE_DEBUG_ID_DIRECTIVE: &str = "@ve-disable-debug-id";
+pub static DISABLE_FILE_SCOPE_DIRECTIVE: &str = "@ve-disable-file-scope";
 
 pub static PACKAGE_IDENTIFIERS: Lazy<HashSet<String>> = Lazy::new(|| {
     let mut set = HashSet::new();
@@ -33,7 +44,7 @@ pub static STYLE_FUNCTIONS: [&str; 14] = [
 ];
 
 pub static CSS_FILE_FILTER_REGEX: Lazy<Regexp> =
-    Lazy::new(|| Regexp::new(r"\.css\.(js|mjs|jsx|ts|tsx)(\?used)?$").unwrap());
+    Lazy::new(|| Regexp::new(r"\.css\.(js|mjs|jsx|ts|tsx)$").unwrap());
 
 pub static DEBUGGABLE_FUNCTION_CONFIG: Lazy<HashMap<String, usize>> = Lazy::new(|| {
     let mut map = HashMap::default();
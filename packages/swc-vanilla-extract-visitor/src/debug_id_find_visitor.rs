@@ -1,72 +1,277 @@
-use std::collections::HashMap;
+use std::collections::{HashMap, VecDeque};
 
 use swc_core::{
-    common::pass::AstNodePath,
+    common::{comments::Comments, pass::AstNodePath, BytePos, Span, Spanned},
     ecma::{
         ast::{
-            CallExpr, Callee, Expr, Ident, Lit, ObjectPatProp, Pat, Prop, PropName, PropOrSpread,
+            CallExpr, Callee, Expr, Id, Ident, Lit, MemberProp, ObjectPatProp, Pat, Prop,
+            PropOrSpread, VarDecl, VarDeclarator,
+        },
+        atoms::JsWord,
+        visit::{
+            fields::{CallExprField, VarDeclField, VarDeclaratorField},
+            AstParentNodeRef, VisitAstPath, VisitWith, VisitWithPath,
         },
-        visit::{AstParentNodeRef, VisitAstPath, VisitWithPath},
     },
 };
 
 use crate::{
-    constants::{DEBUGGABLE_FUNCTION_CONFIG, FILE_SCOPE_PACKAGE_IDENTIFIER},
-    get_relavant_call::get_relavant_call,
+    constants::DEBUGGABLE_FUNCTION_CONFIG,
+    diagnostics::TextEdit,
+    directives::{get_leading_directives, Directive},
+    get_relavant_call::{get_debug_id_position, get_relavant_call, get_wrapper_position},
+    prop_name::get_prop_name,
+    reference_collect_visitor::ReferenceCollectVisitor,
+    report::StyleReport,
+    unwrap_expr::unwrap_expr,
 };
 
+/// Debug id found for a debuggable call.
+pub struct DebugIdCandidate {
+    /// Name derived from the ast path, or the one given by `@ve-debug-id` directive.
+    pub name: String,
+    /// True if name is given by a directive, and should be used as-is.
+    pub is_explicit: bool,
+    pub span: Span,
+    /// Where the call would take the debug id as an argument, `None` for calls without a span.
+    pub insertion: Option<DebugIdInsertion>,
+}
+
+/// Position to insert a debug id argument at, to pass the injected debug id explicitly.
+pub struct DebugIdInsertion {
+    pos: BytePos,
+    /// Separator and `undefined` arguments padding the arguments omitted by the call.
+    prefix: String,
+}
+
+impl DebugIdInsertion {
+    fn new(call_expr: &CallExpr, position: usize) -> Option<Self> {
+        if call_expr.span.is_dummy() {
+            return None;
+        }
+
+        let padding = "undefined, ".repeat(position.saturating_sub(call_expr.args.len()));
+        Some(match call_expr.args.last() {
+            Some(last_arg) => DebugIdInsertion {
+                pos: last_arg.expr.span_hi(),
+                prefix: format!(", {}", padding),
+            },
+            // `style()`, insert before the closing parenthesis
+            None => DebugIdInsertion {
+                pos: call_expr.span.hi - BytePos(1),
+                prefix: padding,
+            },
+        })
+    }
+
+    /// Returns the edit inserting given debug id as a string literal.
+    pub fn edit(&self, debug_id: &str) -> TextEdit {
+        let literal = debug_id
+            .replace('\\', "\\\\")
+            .replace('"', "\\\"")
+            .replace('\n', "\\n");
+        TextEdit::insert(self.pos, format!("{}\"{}\"", self.prefix, literal))
+    }
+}
+
 /// A visitor to find corresponding debug id for the given callexpr, if it's a vanilla-extract style function.
-pub struct DebugIdFindVisitor {
-    pub is_compiled: bool,
-    pub debug_id: Option<String>,
+pub struct DebugIdFindVisitor<'a, C: Comments> {
+    /// Debug ids for each debuggable call in traversal order, `None` if call should not get one.
+    pub debug_ids: VecDeque<Option<DebugIdCandidate>>,
+    /// All style calls in traversal order, collected only if `collect_styles` is set.
+    pub styles: Vec<StyleReport>,
+    /// Debug ids passed as a string literal by style calls, with the span of the call.
+    pub given_debug_ids: Vec<(String, Span)>,
+    pub collect_styles: bool,
 
+    comments: &'a C,
+    default_export_name: &'a str,
+    /// Number of reads of each identifier in the module, to tell compiler temporaries apart.
+    references: &'a HashMap<JsWord, usize>,
     namespace_import: Option<Ident>,
     import_identifiers: HashMap<Ident, String>,
+    wrappers: HashMap<Id, usize>,
 }
 
-impl DebugIdFindVisitor {
+impl<'a, C: Comments> DebugIdFindVisitor<'a, C> {
     pub fn new(
+        comments: &'a C,
+        default_export_name: &'a str,
+        references: &'a HashMap<JsWord, usize>,
         namespace_import: Option<Ident>,
         import_identifiers: HashMap<Ident, String>,
+        wrappers: HashMap<Id, usize>,
     ) -> Self {
         Self {
-            debug_id: None,
-            is_compiled: false,
+            debug_ids: Default::default(),
+            styles: Default::default(),
+            given_debug_ids: Default::default(),
+            collect_styles: false,
 
+            comments,
+            default_export_name,
+            references,
             namespace_import,
             import_identifiers,
+            wrappers,
+        }
+    }
+
+    /// Looks up `@ve-debug-id` / `@ve-ignore` directive from the leading comments of the call,
+    /// then from the enclosing nodes such as the declaration or the property it's assigned to.
+    ///
+    /// The lookup stops at the nearest enclosing call, array or object literal, and at a
+    /// declarator sharing its declaration: a directive there belongs to the outer call or to
+    /// the whole literal, not to each nested call.
+    fn get_directive(
+        &self,
+        call_expr: &CallExpr,
+        ast_path: &AstNodePath<AstParentNodeRef<'_>>,
+    ) -> Option<Directive> {
+        std::iter::once(Some(call_expr.span.lo))
+            .chain(
+                ast_path
+                    .iter()
+                    .rev()
+                    .take_while(|node| !is_directive_boundary(node))
+                    .map(get_span_lo),
+            )
+            .flatten()
+            .flat_map(|pos| get_leading_directives(self.comments, pos))
+            .find(|directive| matches!(directive, Directive::DebugId(..) | Directive::Ignore))
+    }
+
+    /// Returns the kind of the style call, and whether it's a call to a local wrapper.
+    fn get_style_kind(&self, call_expr: &CallExpr) -> Option<(String, bool)> {
+        if let Some(used_export) =
+            get_relavant_call(call_expr, &self.namespace_import, &self.import_identifiers)
+        {
+            return Some((used_export, false));
+        }
+
+        get_wrapper_position(call_expr, &self.wrappers)?;
+        match &call_expr.callee {
+            Callee::Expr(expr) => match &**expr {
+                Expr::Ident(ident) => Some((ident.sym.to_string(), true)),
+                _ => None,
+            },
+            _ => None,
+        }
+    }
+
+    /// Returns the debug id the call passes already, if it's a string literal.
+    fn get_given_debug_id(
+        &self,
+        call_expr: &CallExpr,
+        kind: &str,
+        is_wrapper: bool,
+    ) -> Option<String> {
+        let position = if is_wrapper {
+            get_wrapper_position(call_expr, &self.wrappers)?
+        } else {
+            let max_params = *DEBUGGABLE_FUNCTION_CONFIG.get(kind)?;
+            if call_expr.args.len() != max_params {
+                return None;
+            }
+            max_params - 1
+        };
+
+        match call_expr
+            .args
+            .get(position)
+            .map(|arg| unwrap_expr(&arg.expr))
+        {
+            Some(Expr::Lit(Lit::Str(str))) => Some(str.value.to_string()),
+            _ => None,
         }
     }
 }
 
-fn extract_name<'r>(node: AstParentNodeRef<'r>) -> Option<String> {
-    match node {
-        AstParentNodeRef::PropOrSpread(prop, _) => {
-            if let PropOrSpread::Prop(prop) = prop {
-                if let Prop::KeyValue(key_value) = &**prop {
-                    if let PropName::Ident(ident) = &key_value.key {
-                        return Some(ident.sym.to_string());
-                    }
-                }
+/// Returns the top level binding containing the node at given ast path, and whether it's in the
+/// default export. Calls nested in functions or classes not bound at the top level have none.
+fn get_top_level_binding(ast_path: &AstNodePath<AstParentNodeRef<'_>>) -> (Option<String>, bool) {
+    for node in ast_path.iter() {
+        match node {
+            AstParentNodeRef::VarDeclarator(declarator, _) => {
+                return match &declarator.name {
+                    Pat::Ident(ident) => (Some(ident.id.sym.to_string()), false),
+                    // `const [themeClass, vars] = createTheme()`
+                    Pat::Array(array) => match array.elems.first() {
+                        Some(Some(Pat::Ident(ident))) => (Some(ident.id.sym.to_string()), false),
+                        _ => (None, false),
+                    },
+                    _ => (None, false),
+                };
             }
+            AstParentNodeRef::FnDecl(fn_decl, _) => {
+                return (Some(fn_decl.ident.sym.to_string()), false);
+            }
+            AstParentNodeRef::ClassDecl(class_decl, _) => {
+                return (Some(class_decl.ident.sym.to_string()), false);
+            }
+            AstParentNodeRef::ModuleDecl(module_decl, _)
+                if module_decl.is_export_default_expr() || module_decl.is_export_default_decl() =>
+            {
+                return (None, true);
+            }
+            AstParentNodeRef::Function(..)
+            | AstParentNodeRef::ArrowExpr(..)
+            | AstParentNodeRef::Class(..) => return (None, false),
+            _ => {}
         }
-        AstParentNodeRef::ObjectPatProp(pat_prop, _) => {
-            if let ObjectPatProp::KeyValue(key_value) = pat_prop {
-                if let PropName::Ident(ident) = &key_value.key {
-                    return Some(ident.sym.to_string());
-                }
+    }
+
+    (None, false)
+}
+
+/// Returns start position of the nodes directive comments can be attached to.
+/// Returns true if directives above given node do not apply to the calls nested in it.
+fn is_directive_boundary(node: &AstParentNodeRef<'_>) -> bool {
+    match node {
+        AstParentNodeRef::CallExpr(..)
+        | AstParentNodeRef::NewExpr(..)
+        | AstParentNodeRef::ArrayLit(..)
+        | AstParentNodeRef::ObjectLit(..) => true,
+        AstParentNodeRef::VarDecl(var_decl, _) => var_decl.decls.len() > 1,
+        _ => false,
+    }
+}
+
+fn get_span_lo(node: &AstParentNodeRef<'_>) -> Option<BytePos> {
+    match node {
+        AstParentNodeRef::ModuleItem(node, _) => Some(node.span_lo()),
+        AstParentNodeRef::ModuleDecl(node, _) => Some(node.span_lo()),
+        AstParentNodeRef::Stmt(node, _) => Some(node.span_lo()),
+        AstParentNodeRef::Decl(node, _) => Some(node.span_lo()),
+        AstParentNodeRef::VarDecl(node, _) => Some(node.span_lo()),
+        AstParentNodeRef::VarDeclarator(node, _) => Some(node.span_lo()),
+        AstParentNodeRef::PropOrSpread(node, _) => Some(node.span_lo()),
+        AstParentNodeRef::Prop(node, _) => Some(node.span_lo()),
+        AstParentNodeRef::ExprOrSpread(node, _) => Some(node.span_lo()),
+        AstParentNodeRef::Expr(node, _) => Some(node.span_lo()),
+        _ => None,
+    }
+}
+
+fn extract_name<'r>(node: AstParentNodeRef<'r>, default_export_name: &str) -> Option<String> {
+    match node {
+        AstParentNodeRef::PropOrSpread(PropOrSpread::Prop(prop), _) => {
+            // The sanitizer takes care of characters of string keys not allowed in a debug id
+            if let Prop::KeyValue(key_value) = &**prop {
+                return get_prop_name(&key_value.key);
             }
         }
+        AstParentNodeRef::ObjectPatProp(ObjectPatProp::KeyValue(key_value), _) => {
+            return get_prop_name(&key_value.key);
+        }
         AstParentNodeRef::VarDeclarator(declarator, _) => {
             match &declarator.name {
                 Pat::Ident(ident) => {
                     return Some(ident.sym.to_string());
                 }
                 Pat::Array(array) => {
-                    if let Some(elem) = &array.elems[0] {
-                        if let Pat::Ident(ident) = &*elem {
-                            return Some(ident.sym.to_string());
-                        }
+                    if let Some(Some(Pat::Ident(ident))) = array.elems.first() {
+                        return Some(ident.sym.to_string());
                     }
                 }
                 _ => return None,
@@ -75,10 +280,10 @@ fn extract_name<'r>(node: AstParentNodeRef<'r>) -> Option<String> {
         AstParentNodeRef::FnDecl(fn_decl, _) => {
             return Some(fn_decl.ident.sym.to_string());
         }
-        AstParentNodeRef::ModuleDecl(module_decl, _) => {
-            if module_decl.is_export_default_expr() || module_decl.is_export_default_decl() {
-                return Some("default".to_string());
-            }
+        AstParentNodeRef::ModuleDecl(module_decl, _)
+            if module_decl.is_export_default_expr() || module_decl.is_export_default_decl() =>
+        {
+            return Some(default_export_name.to_string());
         }
         _ => {}
     };
@@ -86,52 +291,159 @@ fn extract_name<'r>(node: AstParentNodeRef<'r>) -> Option<String> {
     None
 }
 
-fn get_debug_id<'r>(ast_path: &mut AstNodePath<AstParentNodeRef<'r>>) -> Option<String> {
-    // When we arrived here, we no longer cares about keeping ast_path in sync, will just mutate it.
+/// Returns true if given binding looks like a temporary variable emitted by a compiler: its name
+/// starts with `_`, and it's only read by the given declarators following it.
+fn is_compiler_temp(
+    name: &JsWord,
+    following: &[VarDeclarator],
+    references: &HashMap<JsWord, usize>,
+) -> bool {
+    if !name.starts_with('_') {
+        return false;
+    }
 
-    let first_relevant_parent = ast_path.last().clone();
+    let mut reference_collect_visitor = ReferenceCollectVisitor::default();
+    for declarator in following {
+        declarator.visit_with(&mut reference_collect_visitor);
+    }
+    reference_collect_visitor.references.get(name) == references.get(name)
+}
 
-    if let Some(first_relevant_parent) = first_relevant_parent {
-        // Special case: Handle `export const [themeClass, vars] = createTheme({});`
-        // when it's already been compiled into this:
-        //
-        // var _createTheme = createTheme({}),
-        //   _createTheme2 = _slicedToArray(_createTheme, 2),
-        //   themeClass = _createTheme2[0],
-        //   vars = _createTheme2[1];
-        let parent = ast_path.last().clone(); //do not take, if this condition doesn't match we'll need to reuse last marker
-        if let Some(parent) = parent {
-            if let AstParentNodeRef::VarDecl(decl, _) = parent {
-                if decl.decls.len() == 4 {
-                    let theme_declarator = decl.decls.get(0).expect("Should exists");
-                    let class_name_declarator = decl.decls.get(2).expect("Should exists");
-
-                    let valid_theme_decl =
-                        if let Some(theme_declarator_init) = theme_declarator.init.as_ref() {
-                            if let Expr::Call(call) = &**theme_declarator_init {
-                                if let Callee::Expr(callee) = &call.callee {
-                                    if let Expr::Ident(callee_ident) = &**callee {
-                                        "createTheme" == &*callee_ident.sym
-                                    } else {
-                                        false
-                                    }
-                                } else {
-                                    false
-                                }
-                            } else {
-                                false
-                            }
-                        } else {
-                            false
-                        };
-
-                    if valid_theme_decl {
-                        if let Pat::Ident(class_name_decl_ident) = &class_name_declarator.name {
-                            return Some(class_name_decl_ident.sym.to_string());
-                        }
-                    }
+/// Given the declarator initialized with the style call (directly, or wrapped by a helper like
+/// `_sliced_to_array(createTheme(...), 2)`), follows the compiler temporary through the subsequent
+/// declarators and returns the name of the bindings destructured from it. Recognizes:
+///
+/// ```js
+/// // Babel
+/// var _createTheme = createTheme({}),
+///   _createTheme2 = _slicedToArray(_createTheme, 2),
+///   themeClass = _createTheme2[0],
+///   vars = _createTheme2[1];
+/// // SWC (`_sliced_to_array`), TypeScript (`__read`)
+/// var _createTheme = _sliced_to_array(createTheme({}), 2), themeClass = _createTheme[0];
+/// // Loose mode array / object destructuring
+/// var _createTheme = createTheme({}), themeClass = _createTheme[0];
+/// var _styles = styleVariants({}), primary = _styles.primary, secondary = _styles.secondary;
+/// ```
+///
+/// Like `const [themeClass, vars] = createTheme({})`, array destructuring is named after its first
+/// element. Object destructuring is named after all of its bindings, i.e `primary_secondary`.
+fn get_destructured_name(
+    decl: &VarDecl,
+    index: usize,
+    references: &HashMap<JsWord, usize>,
+) -> Option<String> {
+    let mut temp_binding = match &decl.decls.get(index)?.name {
+        Pat::Ident(ident) => ident.id.sym.clone(),
+        _ => return None,
+    };
+    let following = &decl.decls[index + 1..];
+    // Hand written declarations, i.e `const button = recipe({}), variants = button.variants;`,
+    // keep the name of the declarator the call is assigned to
+    if !is_compiler_temp(&temp_binding, following, references) {
+        return None;
+    }
+
+    let is_temp_binding = |expr: &Expr, temp_binding: &JsWord| matches!(unwrap_expr(expr), Expr::Ident(ident) if ident.sym == *temp_binding);
+
+    // Destructured bindings with the array index they are read from, if any
+    let mut bindings: Vec<(Option<f64>, &JsWord)> = vec![];
+    for (offset, declarator) in following.iter().enumerate() {
+        let name = match &declarator.name {
+            Pat::Ident(ident) => &ident.id.sym,
+            _ => continue,
+        };
+        let init = match declarator.init.as_deref() {
+            Some(init) => unwrap_expr(init),
+            None => continue,
+        };
+
+        let is_next_temp = match init {
+            // `_createTheme2 = _slicedToArray(_createTheme, 2)`
+            Expr::Call(call_expr) => call_expr
+                .args
+                .first()
+                .is_some_and(|arg| is_temp_binding(&arg.expr, &temp_binding)),
+            // `_ref2 = _ref`
+            Expr::Ident(..) => is_temp_binding(init, &temp_binding),
+            // `themeClass = _createTheme2[0]`, `primary = _styles.primary`
+            Expr::Member(member_expr) if is_temp_binding(&member_expr.obj, &temp_binding) => {
+                let index = match &member_expr.prop {
+                    MemberProp::Computed(computed) => match &*computed.expr {
+                        Expr::Lit(Lit::Num(num)) => Some(num.value),
+                        _ => None,
+                    },
+                    _ => None,
+                };
+                bindings.push((index, name));
+                false
+            }
+            _ => false,
+        };
+
+        if is_next_temp {
+            if !is_compiler_temp(name, &following[offset + 1..], references) {
+                return None;
+            }
+            temp_binding = name.clone();
+            bindings.clear();
+        }
+    }
+
+    if bindings.iter().any(|(index, _)| index.is_some()) {
+        return bindings
+            .iter()
+            .find(|(index, _)| *index == Some(0.0))
+            .map(|(_, name)| name.to_string());
+    }
+
+    let names: Vec<&str> = bindings.iter().map(|(_, name)| &***name).collect();
+    (!names.is_empty()).then(|| names.join("_"))
+}
+
+/// Looks up the closest variable declarator the call is initializing, skipping wrapping
+/// expressions such as helper calls, and recovers the name of transpiled destructuring if any.
+fn get_transpiled_destructured_name(
+    ast_path: &AstNodePath<AstParentNodeRef<'_>>,
+    references: &HashMap<JsWord, usize>,
+) -> Option<String> {
+    for (idx, node) in ast_path.iter().enumerate().rev() {
+        match node {
+            AstParentNodeRef::VarDeclarator(_, VarDeclaratorField::Init) => {
+                if let Some(AstParentNodeRef::VarDecl(decl, VarDeclField::Decls(index))) =
+                    idx.checked_sub(1).and_then(|idx| ast_path.get(idx))
+                {
+                    return get_destructured_name(decl, *index, references);
                 }
+                return None;
             }
+            AstParentNodeRef::Expr(..)
+            | AstParentNodeRef::ExprOrSpread(..)
+            | AstParentNodeRef::CallExpr(_, CallExprField::Args(..))
+            | AstParentNodeRef::ParenExpr(..)
+            | AstParentNodeRef::TsAsExpr(..)
+            | AstParentNodeRef::TsNonNullExpr(..) => {}
+            _ => return None,
+        }
+    }
+
+    None
+}
+
+fn get_debug_id<'r>(
+    ast_path: &mut AstNodePath<AstParentNodeRef<'r>>,
+    default_export_name: &str,
+    references: &HashMap<JsWord, usize>,
+) -> Option<String> {
+    // When we arrived here, we no longer cares about keeping ast_path in sync, will just mutate it.
+
+    let first_relevant_parent = ast_path.last();
+
+    if let Some(first_relevant_parent) = first_relevant_parent {
+        // Special case: destructuring already downleveled by another compiler, recover the
+        // original binding name instead of the temporary variable.
+        if let Some(name) = get_transpiled_destructured_name(ast_path, references) {
+            return Some(name);
         }
 
         return match first_relevant_parent {
@@ -142,63 +454,78 @@ fn get_debug_id<'r>(ast_path: &mut AstNodePath<AstParentNodeRef<'r>>) -> Option<
                 let mut names = vec![];
 
                 for path in ast_path.iter().rev() {
-                    let name = extract_name(*path);
+                    let name = extract_name(*path, default_export_name);
                     if let Some(name) = name {
                         names.insert(0, name);
                     }
                 }
 
-                if names.len() > 0 {
+                if !names.is_empty() {
                     Some(names.join("_").to_string())
                 } else {
                     None
                 }
             }
-            _ => extract_name(*first_relevant_parent),
+            _ => extract_name(*first_relevant_parent, default_export_name),
         };
     }
 
     None
 }
 
-impl VisitAstPath for DebugIdFindVisitor {
+impl<'a, C: Comments> VisitAstPath for DebugIdFindVisitor<'a, C> {
     fn visit_call_expr<'ast: 'r, 'r>(
         &mut self,
         call_expr: &'r CallExpr,
         ast_path: &mut AstNodePath<AstParentNodeRef<'r>>,
     ) {
-        if self.is_compiled {
-            return;
-        }
-
-        if let Callee::Expr(expr) = &call_expr.callee {
-            if let Expr::Ident(ident) = &**expr {
-                if &*ident.sym == "require" {
-                    if let Some(arg) = call_expr.args.get(0) {
-                        if let Expr::Lit(expr) = &*arg.expr {
-                            if let Lit::Str(expr) = expr {
-                                if &*expr.value == FILE_SCOPE_PACKAGE_IDENTIFIER {
-                                    // If file scope import is found it means the file has already been compiled
-                                    self.is_compiled = true;
-                                }
-                            }
-                        }
-                    }
-                }
+        if let Some((kind, is_wrapper)) = self.get_style_kind(call_expr) {
+            let given_debug_id = self.get_given_debug_id(call_expr, &kind, is_wrapper);
+            if let Some(given_debug_id) = &given_debug_id {
+                self.given_debug_ids
+                    .push((given_debug_id.clone(), call_expr.span));
             }
-        }
-
-        let used_export =
-            get_relavant_call(call_expr, &self.namespace_import, &self.import_identifiers);
 
-        if let Some(used_export) = used_export {
-            if let Some(max_params) = DEBUGGABLE_FUNCTION_CONFIG.get(&used_export) {
-                if call_expr.args.len() < *max_params {
-                    self.debug_id = get_debug_id(ast_path);
-                }
+            if self.collect_styles {
+                let (local_name, is_default_export) = get_top_level_binding(ast_path);
+                self.styles.push(StyleReport {
+                    debug_id: given_debug_id,
+                    kind,
+                    is_wrapper,
+                    local_name,
+                    export_name: is_default_export.then(|| "default".to_string()),
+                    span: call_expr.span,
+                });
             }
         }
 
+        if let Some(position) = get_debug_id_position(
+            call_expr,
+            &self.namespace_import,
+            &self.import_identifiers,
+            &self.wrappers,
+        ) {
+            let debug_id =
+                match self.get_directive(call_expr, ast_path) {
+                    Some(Directive::DebugId(name)) => Some(DebugIdCandidate {
+                        name,
+                        is_explicit: true,
+                        span: call_expr.span,
+                        insertion: DebugIdInsertion::new(call_expr, position),
+                    }),
+                    Some(Directive::Ignore) => None,
+                    _ => get_debug_id(ast_path, self.default_export_name, self.references).map(
+                        |name| DebugIdCandidate {
+                            name,
+                            is_explicit: false,
+                            span: call_expr.span,
+                            insertion: DebugIdInsertion::new(call_expr, position),
+                        },
+                    ),
+                };
+            self.debug_ids.push_back(debug_id);
+        }
+
         call_expr.visit_children_with_path(self, ast_path);
     }
 }
@@ -1,47 +1,63 @@
-use std::collections::HashMap;
+use std::collections::{HashMap, VecDeque};
 
-use swc_core::ecma::{
-    ast::{CallExpr, Expr, ExprOrSpread, Ident, Lit, Str},
-    visit::{VisitMut, VisitMutWith},
+use swc_core::{
+    common::DUMMY_SP,
+    ecma::{
+        ast::{CallExpr, Expr, ExprOrSpread, Id, Ident, Lit, Str},
+        atoms::js_word,
+        visit::{VisitMut, VisitMutWith},
+    },
 };
 
-use crate::{constants::DEBUGGABLE_FUNCTION_CONFIG, get_relavant_call::get_relavant_call};
+use crate::get_relavant_call::get_debug_id_position;
 
 /// A visitor actually injects debugid into given callexpr, if given call expr is a vanilla-extract style function.
 pub struct DebugIdInjectVisitor {
-    pub debug_id: Option<String>,
+    /// Debug ids found by `DebugIdFindVisitor`, consumed in the same traversal order.
+    pub debug_ids: VecDeque<Option<String>>,
     namespace_import: Option<Ident>,
     import_identifiers: HashMap<Ident, String>,
+    wrappers: HashMap<Id, usize>,
 }
 
 impl DebugIdInjectVisitor {
     pub fn new(
         namespace_import: Option<Ident>,
         import_identifiers: HashMap<Ident, String>,
+        wrappers: HashMap<Id, usize>,
     ) -> Self {
         DebugIdInjectVisitor {
-            debug_id: None,
+            debug_ids: Default::default(),
             namespace_import,
             import_identifiers,
+            wrappers,
         }
     }
 }
 
 impl VisitMut for DebugIdInjectVisitor {
     fn visit_mut_call_expr(&mut self, call_expr: &mut CallExpr) {
-        let used_export =
-            get_relavant_call(call_expr, &self.namespace_import, &self.import_identifiers);
-
-        if let Some(used_export) = used_export {
-            if let Some(max_params) = DEBUGGABLE_FUNCTION_CONFIG.get(&used_export) {
-                if call_expr.args.len() < *max_params {
-                    if let Some(debug_id) = self.debug_id.take() {
-                        call_expr.args.push(ExprOrSpread {
-                            spread: None,
-                            expr: Box::new(Expr::Lit(Lit::Str(Str::from(debug_id)))),
-                        })
-                    }
+        let position = get_debug_id_position(
+            call_expr,
+            &self.namespace_import,
+            &self.import_identifiers,
+            &self.wrappers,
+        );
+
+        if let Some(position) = position {
+            if let Some(Some(debug_id)) = self.debug_ids.pop_front() {
+                // Wrappers may forward a parameter after the ones omitted by the call
+                while call_expr.args.len() < position {
+                    call_expr.args.push(ExprOrSpread {
+                        spread: None,
+                        expr: Box::new(Expr::Ident(Ident::new(js_word!("undefined"), DUMMY_SP))),
+                    });
                 }
+
+                call_expr.args.push(ExprOrSpread {
+                    spread: None,
+                    expr: Box::new(Expr::Lit(Lit::Str(Str::from(debug_id)))),
+                })
             }
         }
 
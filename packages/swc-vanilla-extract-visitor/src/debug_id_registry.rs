@@ -0,0 +1,147 @@
+use std::collections::HashMap;
+
+use swc_core::common::Span;
+
+use crate::DebugIdSanitizer;
+
+/// Tracks debug ids issued within a file, so calls deriving the same debug id get distinct ones.
+#[derive(Debug, Default)]
+pub struct DebugIdRegistry {
+    /// Span of the call each issued debug id was first used for.
+    issued: HashMap<String, Span>,
+    /// Sanitizer capping the length of issued debug ids, suffix included.
+    sanitizer: Option<DebugIdSanitizer>,
+}
+
+/// Debug id issued by the registry.
+pub struct RegisteredDebugId {
+    pub debug_id: String,
+    /// Span of the call which used the requested debug id first, if it collided.
+    pub collided_with: Option<Span>,
+}
+
+impl DebugIdRegistry {
+    /// Creates a registry truncating issued debug ids with given sanitizer, if any. Debug ids are
+    /// expected to be sanitized already, only their length is capped.
+    pub fn new(sanitizer: Option<DebugIdSanitizer>) -> Self {
+        DebugIdRegistry {
+            issued: Default::default(),
+            sanitizer,
+        }
+    }
+
+    fn truncate(&self, debug_id: String) -> String {
+        match &self.sanitizer {
+            Some(sanitizer) => sanitizer.truncate(&debug_id),
+            None => debug_id,
+        }
+    }
+
+    /// Reserves a debug id the call at given span passes itself, so no other call is issued it.
+    pub fn reserve(&mut self, debug_id: String, span: Span) {
+        self.issued.entry(debug_id).or_insert(span);
+    }
+
+    /// Registers the debug id of the call at given span. If the debug id has been issued already,
+    /// appends the first free numeric suffix starting from `_2`, i.e `root`, `root_2`, `root_3`.
+    /// The suffix is appended before truncating, so suffixed ids respect the length cap.
+    pub fn register(&mut self, debug_id: String, span: Span) -> RegisteredDebugId {
+        let truncated = self.truncate(debug_id.clone());
+        let collided_with = match self.issued.get(&truncated) {
+            Some(first_span) => *first_span,
+            None => {
+                self.issued.insert(truncated.clone(), span);
+                return RegisteredDebugId {
+                    debug_id: truncated,
+                    collided_with: None,
+                };
+            }
+        };
+
+        let mut suffix = 2;
+        let debug_id = loop {
+            let candidate = self.truncate(format!("{}_{}", debug_id, suffix));
+            if !self.issued.contains_key(&candidate) {
+                break candidate;
+            }
+            suffix += 1;
+        };
+        self.issued.insert(debug_id.clone(), span);
+
+        RegisteredDebugId {
+            debug_id,
+            collided_with: Some(collided_with),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use pretty_assertions::assert_eq;
+    use swc_core::common::{BytePos, DUMMY_SP};
+
+    use super::*;
+
+    #[test]
+    fn appends_suffix_on_collision() {
+        let mut registry = DebugIdRegistry::default();
+        let first_span = Span::new(BytePos(1), BytePos(2), Default::default());
+
+        let first = registry.register("root".to_string(), first_span);
+        assert_eq!(first.debug_id, "root");
+        assert_eq!(first.collided_with, None);
+
+        let second = registry.register("root".to_string(), DUMMY_SP);
+        assert_eq!(second.debug_id, "root_2");
+        assert_eq!(second.collided_with, Some(first_span));
+
+        let third = registry.register("root".to_string(), DUMMY_SP);
+        assert_eq!(third.debug_id, "root_3");
+        assert_eq!(third.collided_with, Some(first_span));
+    }
+
+    #[test]
+    fn skips_suffix_already_issued() {
+        let mut registry = DebugIdRegistry::default();
+
+        registry.register("root_2".to_string(), DUMMY_SP);
+        registry.register("root".to_string(), DUMMY_SP);
+
+        assert_eq!(
+            registry.register("root".to_string(), DUMMY_SP).debug_id,
+            "root_3"
+        );
+        assert_eq!(
+            registry.register("other".to_string(), DUMMY_SP).debug_id,
+            "other"
+        );
+    }
+
+    #[test]
+    fn caps_length_with_suffix() {
+        let mut registry = DebugIdRegistry::new(Some(DebugIdSanitizer::new(16)));
+
+        let first = registry.register("averyveryverylongname".to_string(), DUMMY_SP);
+        let second = registry.register("averyveryverylongname".to_string(), DUMMY_SP);
+
+        assert_eq!(first.debug_id.len(), 16);
+        assert_eq!(second.debug_id.len(), 16);
+        assert_ne!(first.debug_id, second.debug_id);
+        assert_eq!(
+            registry.register("short".to_string(), DUMMY_SP).debug_id,
+            "short"
+        );
+    }
+
+    #[test]
+    fn skips_reserved_debug_ids() {
+        let mut registry = DebugIdRegistry::default();
+        let given_span = Span::new(BytePos(1), BytePos(2), Default::default());
+
+        registry.reserve("root".to_string(), given_span);
+        let registered = registry.register("root".to_string(), DUMMY_SP);
+
+        assert_eq!(registered.debug_id, "root_2");
+        assert_eq!(registered.collided_with, Some(given_span));
+    }
+}
@@ -0,0 +1,182 @@
+use unicode_normalization::{char::is_combining_mark, UnicodeNormalization};
+
+/// Default maximum length of a sanitized debug id.
+pub const DEFAULT_MAX_LENGTH: usize = 64;
+/// Length of the hash suffix appended to truncated debug ids, including the separator.
+const HASH_SUFFIX_LENGTH: usize = 7;
+/// Shortest max length keeping at least one character of the id in front of the hash suffix.
+pub const MIN_MAX_LENGTH: usize = HASH_SUFFIX_LENGTH + 1;
+
+/// Turns derived debug ids into readable CSS identifier fragments, so vanilla-extract does not
+/// need to escape them into class names.
+///
+/// - Latin letters with diacritics are transliterated into ASCII (`é` -> `e`, `ß` -> `ss`)
+/// - Other non-ASCII characters are escaped into their code point (`中` -> `u4e2d`)
+/// - Any other character not allowed in an identifier (`$`, spaces, ...) becomes `_`
+/// - Ids starting with a digit, or a hyphen followed by a digit, are prefixed with `_`
+/// - Ids longer than the max length are truncated, with a hash of the full id appended to keep
+///   them distinct
+#[derive(Debug, Clone)]
+pub struct DebugIdSanitizer {
+    max_length: usize,
+}
+
+impl Default for DebugIdSanitizer {
+    fn default() -> Self {
+        DebugIdSanitizer {
+            max_length: DEFAULT_MAX_LENGTH,
+        }
+    }
+}
+
+/// Transliterates letters which do not decompose into an ASCII base letter.
+fn transliterate(c: char) -> Option<&'static str> {
+    Some(match c {
+        'ß' => "ss",
+        'æ' => "ae",
+        'Æ' => "AE",
+        'œ' => "oe",
+        'Œ' => "OE",
+        'ø' => "o",
+        'Ø' => "O",
+        'đ' | 'ð' => "d",
+        'Đ' | 'Ð' => "D",
+        'ł' => "l",
+        'Ł' => "L",
+        'þ' => "th",
+        'Þ' => "TH",
+        'ı' => "i",
+        _ => return None,
+    })
+}
+
+/// FNV-1a hash, stable across platforms and releases unlike `DefaultHasher`.
+fn hash(value: &str) -> u32 {
+    value.bytes().fold(0x811c9dc5, |hash, byte| {
+        (hash ^ byte as u32).wrapping_mul(0x01000193)
+    })
+}
+
+impl DebugIdSanitizer {
+    /// Creates a sanitizer truncating debug ids longer than given length. Lengths shorter than
+    /// the hash suffix are raised to fit it.
+    pub fn new(max_length: usize) -> Self {
+        DebugIdSanitizer {
+            max_length: max_length.max(MIN_MAX_LENGTH),
+        }
+    }
+
+    pub fn sanitize(&self, debug_id: &str) -> String {
+        self.truncate(&self.sanitize_chars(debug_id))
+    }
+
+    /// Sanitizes the characters of given debug id, without truncating it.
+    pub(crate) fn sanitize_chars(&self, debug_id: &str) -> String {
+        let mut sanitized = String::with_capacity(debug_id.len());
+        // Collapses runs of replaced characters into a single `_`
+        let mut replaced = false;
+
+        for c in debug_id.nfkd().filter(|c| !is_combining_mark(*c)) {
+            if c.is_ascii_alphanumeric() || c == '_' || c == '-' {
+                sanitized.push(c);
+                replaced = false;
+            } else if let Some(transliterated) = transliterate(c) {
+                sanitized.push_str(transliterated);
+                replaced = false;
+            } else if !c.is_ascii() && c.is_alphanumeric() {
+                sanitized.push_str(&format!("u{:x}", c as u32));
+                replaced = false;
+            } else if !replaced {
+                sanitized.push('_');
+                replaced = true;
+            }
+        }
+
+        let mut chars = sanitized.chars();
+        let starts_with_digit = match chars.next() {
+            Some(c) if c.is_ascii_digit() => true,
+            Some('-') => chars.next().is_none_or(|c| c.is_ascii_digit()),
+            _ => false,
+        };
+        if starts_with_digit {
+            sanitized.insert(0, '_');
+        }
+
+        sanitized
+    }
+
+    /// Truncates given sanitized debug id to the max length, appending a hash of the full id.
+    pub(crate) fn truncate(&self, sanitized: &str) -> String {
+        if sanitized.len() <= self.max_length {
+            return sanitized.to_string();
+        }
+
+        // Sanitized id only consists of ASCII, safe to truncate at any byte
+        format!(
+            "{}_{:06x}",
+            &sanitized[..self.max_length - HASH_SUFFIX_LENGTH],
+            hash(sanitized) & 0xffffff
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use pretty_assertions::assert_eq;
+
+    use super::*;
+
+    #[test]
+    fn keeps_valid_identifiers() {
+        let sanitizer = DebugIdSanitizer::default();
+
+        assert_eq!(sanitizer.sanitize("test_one_two"), "test_one_two");
+        assert_eq!(sanitizer.sanitize("_createTheme"), "_createTheme");
+        assert_eq!(sanitizer.sanitize("button-root"), "button-root");
+    }
+
+    #[test]
+    fn replaces_invalid_characters() {
+        let sanitizer = DebugIdSanitizer::default();
+
+        assert_eq!(sanitizer.sanitize("$root"), "_root");
+        assert_eq!(sanitizer.sanitize("primary button"), "primary_button");
+        assert_eq!(sanitizer.sanitize("a  &  b"), "a_b");
+        assert_eq!(sanitizer.sanitize("styles:hover"), "styles_hover");
+    }
+
+    #[test]
+    fn transliterates_non_ascii_letters() {
+        let sanitizer = DebugIdSanitizer::default();
+
+        assert_eq!(sanitizer.sanitize("crème_brûlée"), "creme_brulee");
+        assert_eq!(sanitizer.sanitize("straße"), "strasse");
+        assert_eq!(sanitizer.sanitize("Øre"), "Ore");
+        assert_eq!(sanitizer.sanitize("ﬁle"), "file");
+        assert_eq!(sanitizer.sanitize("中文"), "u4e2du6587");
+    }
+
+    #[test]
+    fn escapes_leading_digits() {
+        let sanitizer = DebugIdSanitizer::default();
+
+        assert_eq!(sanitizer.sanitize("1column"), "_1column");
+        assert_eq!(sanitizer.sanitize("-1column"), "_-1column");
+        assert_eq!(sanitizer.sanitize("-"), "_-");
+        assert_eq!(sanitizer.sanitize("-column"), "-column");
+    }
+
+    #[test]
+    fn caps_length() {
+        let sanitizer = DebugIdSanitizer::new(16);
+
+        let first = sanitizer.sanitize("averyveryverylongname_one");
+        let second = sanitizer.sanitize("averyveryverylongname_two");
+
+        assert_eq!(first.len(), 16);
+        assert_eq!(&first[..9], "averyvery");
+        assert_ne!(first, second);
+        assert_eq!(first, sanitizer.sanitize("averyveryverylongname_one"));
+        assert_eq!(sanitizer.sanitize("short"), "short");
+    }
+}
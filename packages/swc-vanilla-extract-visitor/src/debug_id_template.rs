@@ -0,0 +1,95 @@
+/// Values available to the placeholders of a debug id template.
+pub struct DebugIdTemplateContext<'a> {
+    /// Name derived from the bindings / properties the call is assigned to, i.e `button_root`.
+    pub name: &'a str,
+    /// File name without its `.css.ts` extension.
+    pub file_stem: &'a str,
+    /// Name of the directory containing the file.
+    pub dir: &'a str,
+    /// 1-based line of the call.
+    pub line: usize,
+    /// 0-based index of the debug id within the file.
+    pub index: usize,
+}
+
+/// Template to build debug ids from, i.e `[fileStem]_[name]` or `[dir]-[name]-L[line]`.
+///
+/// Supported placeholders are `[name]`, `[fileStem]`, `[dir]`, `[line]` and `[index]`. Unknown
+/// placeholders are kept as-is.
+#[derive(Debug, Clone)]
+pub struct DebugIdTemplate {
+    template: String,
+}
+
+impl DebugIdTemplate {
+    pub fn new(template: &str) -> Self {
+        DebugIdTemplate {
+            template: template.to_string(),
+        }
+    }
+
+    /// Returns true if template refers to the line of the call, which requires source map lookup.
+    pub fn uses_line(&self) -> bool {
+        self.template.contains("[line]")
+    }
+
+    pub fn expand(&self, context: &DebugIdTemplateContext) -> String {
+        let mut debug_id = String::with_capacity(self.template.len());
+        let mut rest = self.template.as_str();
+
+        while let Some(start) = rest.find('[') {
+            debug_id.push_str(&rest[..start]);
+            rest = &rest[start..];
+
+            let end = match rest.find(']') {
+                Some(end) => end,
+                None => break,
+            };
+
+            match &rest[1..end] {
+                "name" => debug_id.push_str(context.name),
+                "fileStem" => debug_id.push_str(context.file_stem),
+                "dir" => debug_id.push_str(context.dir),
+                "line" => debug_id.push_str(&context.line.to_string()),
+                "index" => debug_id.push_str(&context.index.to_string()),
+                _ => debug_id.push_str(&rest[..=end]),
+            }
+            rest = &rest[end + 1..];
+        }
+        debug_id.push_str(rest);
+
+        debug_id
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use pretty_assertions::assert_eq;
+
+    use super::*;
+
+    #[test]
+    fn expands_placeholders() {
+        let context = DebugIdTemplateContext {
+            name: "root",
+            file_stem: "button",
+            dir: "components",
+            line: 12,
+            index: 3,
+        };
+
+        assert_eq!(DebugIdTemplate::new("[name]").expand(&context), "root");
+        assert_eq!(
+            DebugIdTemplate::new("[fileStem]_[name]").expand(&context),
+            "button_root"
+        );
+        assert_eq!(
+            DebugIdTemplate::new("[dir]-[name]-L[line]-[index]").expand(&context),
+            "components-root-L12-3"
+        );
+        assert_eq!(
+            DebugIdTemplate::new("[unknown]_[name]_[").expand(&context),
+            "[unknown]_root_["
+        );
+    }
+}
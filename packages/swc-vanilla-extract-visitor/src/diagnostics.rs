@@ -0,0 +1,400 @@
+use std::{
+    collections::HashMap,
+    fmt,
+    str::FromStr,
+    sync::{Arc, Mutex},
+};
+
+use swc_core::common::{
+    errors::{self, DiagnosticBuilder, DiagnosticId, HANDLER},
+    BytePos, Span,
+};
+
+/// Severity a check reports its diagnostics with.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Severity {
+    /// Fails the transform.
+    #[default]
+    Error,
+    Warning,
+    /// Disables the check.
+    Off,
+}
+
+impl FromStr for Severity {
+    type Err = String;
+
+    /// Parses the severity from its name in plugin options, `error`, `warning` or `off`.
+    fn from_str(severity: &str) -> Result<Self, Self::Err> {
+        match severity {
+            "error" => Ok(Severity::Error),
+            "warning" => Ok(Severity::Warning),
+            "off" => Ok(Severity::Off),
+            _ => Err(format!("Unsupported severity: {}", severity)),
+        }
+    }
+}
+
+impl fmt::Display for Severity {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            Severity::Error => "error",
+            Severity::Warning => "warning",
+            Severity::Off => "off",
+        })
+    }
+}
+
+/// Checks the transform performs. Each rule has a stable code, i.e `VE001`, and a name matching
+/// its plugin option, i.e `styleCallsOutsideStyleFiles`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Rule {
+    /// Style functions called in a file which is not a style file.
+    StyleCallsOutsideStyleFiles,
+    /// Style functions called in a function which does not run while the file is evaluated.
+    LazyStyleCalls,
+    /// Exports of a style file vanilla-extract can't serialise.
+    NonSerializableExports,
+    /// Keys of literal style rules which are neither CSS properties nor nesting keys.
+    UnknownStyleKeys,
+    /// Selectors of `selectors` maps which do not target `&`.
+    UntargetedSelectors,
+    /// Tokens of a theme which do not match the contract created in the same file.
+    ThemeContractMismatches,
+    /// Top level styles which are neither used nor exported.
+    UnusedStyles,
+    /// Imports of a style file matching the import deny list.
+    DeniedImports,
+    /// Calls deriving a debug id already used in the file.
+    DuplicateDebugIds,
+}
+
+impl Rule {
+    pub const ALL: [Rule; 9] = [
+        Rule::StyleCallsOutsideStyleFiles,
+        Rule::LazyStyleCalls,
+        Rule::NonSerializableExports,
+        Rule::UnknownStyleKeys,
+        Rule::UntargetedSelectors,
+        Rule::ThemeContractMismatches,
+        Rule::UnusedStyles,
+        Rule::DeniedImports,
+        Rule::DuplicateDebugIds,
+    ];
+
+    pub fn code(self) -> &'static str {
+        match self {
+            Rule::StyleCallsOutsideStyleFiles => "VE001",
+            Rule::LazyStyleCalls => "VE002",
+            Rule::NonSerializableExports => "VE003",
+            Rule::UnknownStyleKeys => "VE004",
+            Rule::UntargetedSelectors => "VE005",
+            Rule::ThemeContractMismatches => "VE006",
+            Rule::UnusedStyles => "VE007",
+            Rule::DeniedImports => "VE008",
+            Rule::DuplicateDebugIds => "VE009",
+        }
+    }
+
+    /// Name of the rule in plugin options.
+    pub fn name(self) -> &'static str {
+        match self {
+            Rule::StyleCallsOutsideStyleFiles => "styleCallsOutsideStyleFiles",
+            Rule::LazyStyleCalls => "lazyStyleCalls",
+            Rule::NonSerializableExports => "nonSerializableExports",
+            Rule::UnknownStyleKeys => "unknownStyleKeys",
+            Rule::UntargetedSelectors => "untargetedSelectors",
+            Rule::ThemeContractMismatches => "themeContractMismatches",
+            Rule::UnusedStyles => "unusedStyles",
+            Rule::DeniedImports => "deniedImports",
+            Rule::DuplicateDebugIds => "duplicateDebugIds",
+        }
+    }
+
+    /// Severity of the rule unless configured otherwise. Rules for code vanilla-extract throws for
+    /// are errors, the others are warnings.
+    pub fn default_severity(self) -> Severity {
+        match self {
+            Rule::StyleCallsOutsideStyleFiles
+            | Rule::UntargetedSelectors
+            | Rule::ThemeContractMismatches => Severity::Error,
+            Rule::LazyStyleCalls
+            | Rule::NonSerializableExports
+            | Rule::UnknownStyleKeys
+            | Rule::UnusedStyles
+            | Rule::DeniedImports => Severity::Warning,
+            Rule::DuplicateDebugIds => Severity::Off,
+        }
+    }
+
+    /// How to address diagnostics of the rule.
+    pub fn help(self) -> &'static str {
+        match self {
+            Rule::StyleCallsOutsideStyleFiles => {
+                "Move the style into a .css.ts file and import the class name it returns"
+            }
+            Rule::LazyStyleCalls => {
+                "Move the call to the top level of the file, or call the function from there"
+            }
+            Rule::NonSerializableExports => {
+                "Export plain values and functions created by `recipe` or `createSprinkles`, or \
+                 only import this file from other style files"
+            }
+            Rule::UnknownStyleKeys => {
+                "Style rules take CSS properties in camel case, pseudo selectors, `selectors`, \
+                 `vars`, and at-rules nested by type under `@media`, `@supports`, `@container` or \
+                 `@layer`"
+            }
+            Rule::UntargetedSelectors => "Use `globalStyle` to style other elements",
+            Rule::ThemeContractMismatches => {
+                "Themes must define exactly the tokens of the contract they implement"
+            }
+            Rule::UnusedStyles => "Export the style, use it, or remove the declaration",
+            Rule::DeniedImports => {
+                "Style files are evaluated at build time, importing components or large modules \
+                 slows the build down. Add the import to `importAllowList` if it's needed"
+            }
+            Rule::DuplicateDebugIds => {
+                "Pass a debug id to one of the calls to tell their class names apart"
+            }
+        }
+    }
+}
+
+impl FromStr for Rule {
+    type Err = String;
+
+    /// Parses the rule from its code, i.e `VE001`, or its name, i.e `styleCallsOutsideStyleFiles`.
+    fn from_str(rule: &str) -> Result<Self, Self::Err> {
+        Rule::ALL
+            .into_iter()
+            .find(|candidate| candidate.code() == rule || candidate.name() == rule)
+            .ok_or_else(|| format!("Unknown rule: {}", rule))
+    }
+}
+
+/// Severity configured for each rule. Rules not configured use their default severity.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct RuleConfig(HashMap<Rule, Severity>);
+
+impl RuleConfig {
+    pub fn new() -> Self {
+        Default::default()
+    }
+
+    pub fn set(&mut self, rule: Rule, severity: Severity) {
+        self.0.insert(rule, severity);
+    }
+
+    pub fn get(&self, rule: Rule) -> Severity {
+        self.0
+            .get(&rule)
+            .copied()
+            .unwrap_or_else(|| rule.default_severity())
+    }
+}
+
+/// Replacement of the source text at a span. Empty spans insert the replacement.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TextEdit {
+    pub span: Span,
+    pub replacement: String,
+}
+
+impl TextEdit {
+    pub fn replace(span: Span, replacement: impl Into<String>) -> Self {
+        TextEdit {
+            span,
+            replacement: replacement.into(),
+        }
+    }
+
+    pub fn insert(pos: BytePos, text: impl Into<String>) -> Self {
+        TextEdit::replace(Span::new(pos, pos, Default::default()), text)
+    }
+}
+
+/// Whether a fix can be applied without review.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Applicability {
+    /// The fix is what the code meant, it can be applied mechanically.
+    MachineApplicable,
+    /// The fix is likely what the code meant, but has to be reviewed, i.e a typo suggestion.
+    #[default]
+    MaybeIncorrect,
+}
+
+impl fmt::Display for Applicability {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            Applicability::MachineApplicable => "machineApplicable",
+            Applicability::MaybeIncorrect => "maybeIncorrect",
+        })
+    }
+}
+
+impl From<Applicability> for errors::Applicability {
+    fn from(applicability: Applicability) -> Self {
+        match applicability {
+            Applicability::MachineApplicable => errors::Applicability::MachineApplicable,
+            Applicability::MaybeIncorrect => errors::Applicability::MaybeIncorrect,
+        }
+    }
+}
+
+/// Edits addressing a diagnostic. Edits of a fix do not overlap, and are meant to be applied
+/// together.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Fix {
+    /// Describes the fix, i.e "Replace with `color`".
+    pub message: String,
+    pub edits: Vec<TextEdit>,
+    /// Whether the fix can be applied without review, `MaybeIncorrect` unless set otherwise.
+    pub applicability: Applicability,
+}
+
+impl Fix {
+    pub fn new(message: impl Into<String>, edits: Vec<TextEdit>) -> Self {
+        Fix {
+            message: message.into(),
+            edits,
+            applicability: Default::default(),
+        }
+    }
+
+    pub fn with_applicability(mut self, applicability: Applicability) -> Self {
+        self.applicability = applicability;
+        self
+    }
+}
+
+/// A problem found by one of the rules.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Diagnostic {
+    pub rule: Rule,
+    /// Severity the diagnostic is reported with, after applying the rule config and strict mode.
+    pub severity: Severity,
+    pub span: Span,
+    pub message: String,
+    /// Related spans with a message, i.e the first use of a duplicated debug id.
+    pub notes: Vec<(Span, String)>,
+    /// Fix to apply mechanically, if the problem has an obvious one.
+    pub fix: Option<Fix>,
+}
+
+impl Diagnostic {
+    /// Creates a diagnostic of given rule, with the default severity of the rule.
+    pub fn new(rule: Rule, span: Span, message: impl Into<String>) -> Self {
+        Diagnostic {
+            rule,
+            severity: rule.default_severity(),
+            span,
+            message: message.into(),
+            notes: vec![],
+            fix: None,
+        }
+    }
+
+    pub fn with_note(mut self, span: Span, message: impl Into<String>) -> Self {
+        self.notes.push((span, message.into()));
+        self
+    }
+
+    pub fn with_fix(mut self, fix: Fix) -> Self {
+        self.fix = Some(fix);
+        self
+    }
+}
+
+/// Log shared with the visitor to read the diagnostics reported while transforming a file, i.e to
+/// return them from a binding along with the transformed code.
+#[derive(Debug, Clone, Default)]
+pub struct DiagnosticLog(Arc<Mutex<Vec<Diagnostic>>>);
+
+impl DiagnosticLog {
+    pub fn new() -> Self {
+        Default::default()
+    }
+
+    /// Takes the diagnostics out of the log, leaving it empty for the next file.
+    pub fn take(&self) -> Vec<Diagnostic> {
+        std::mem::take(
+            &mut *self
+                .0
+                .lock()
+                .expect("Diagnostic log should not be poisoned"),
+        )
+    }
+
+    fn push(&self, diagnostic: Diagnostic) {
+        self.0
+            .lock()
+            .expect("Diagnostic log should not be poisoned")
+            .push(diagnostic);
+    }
+}
+
+/// Reports given diagnostic with its severity, code and the help of its rule, and records it in
+/// the log if any. Diagnostics of rules which are off are dropped.
+pub(crate) fn emit(diagnostic: Diagnostic, log: Option<&DiagnosticLog>) {
+    if diagnostic.severity == Severity::Off {
+        return;
+    }
+
+    if HANDLER.is_set() {
+        HANDLER.with(|handler| {
+            let code = DiagnosticId::Error(diagnostic.rule.code().to_string());
+            let mut builder: DiagnosticBuilder<'_> = match diagnostic.severity {
+                Severity::Warning => {
+                    handler.struct_span_warn_with_code(diagnostic.span, &diagnostic.message, code)
+                }
+                _ => handler.struct_span_err_with_code(diagnostic.span, &diagnostic.message, code),
+            };
+            for (span, note) in &diagnostic.notes {
+                builder.span_note(*span, note);
+            }
+            if let Some(fix) = &diagnostic.fix {
+                builder.multipart_suggestion_with_applicability(
+                    &fix.message,
+                    fix.edits
+                        .iter()
+                        .map(|edit| (edit.span, edit.replacement.clone()))
+                        .collect(),
+                    fix.applicability.into(),
+                );
+            }
+            builder.help(diagnostic.rule.help()).emit();
+        });
+    }
+
+    if let Some(log) = log {
+        log.push(diagnostic);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use pretty_assertions::assert_eq;
+
+    use super::*;
+
+    #[test]
+    fn parses_rules() {
+        assert_eq!("VE005".parse(), Ok(Rule::UntargetedSelectors));
+        assert_eq!("lazyStyleCalls".parse(), Ok(Rule::LazyStyleCalls));
+        assert_eq!(
+            "VE999".parse::<Rule>(),
+            Err("Unknown rule: VE999".to_string())
+        );
+    }
+
+    #[test]
+    fn configures_severities() {
+        let mut rules = RuleConfig::new();
+        rules.set(Rule::UnusedStyles, Severity::Error);
+
+        assert_eq!(rules.get(Rule::UnusedStyles), Severity::Error);
+        assert_eq!(rules.get(Rule::LazyStyleCalls), Severity::Warning);
+        assert_eq!(rules.get(Rule::DuplicateDebugIds), Severity::Off);
+    }
+}
@@ -0,0 +1,56 @@
+use swc_core::common::{comments::Comments, BytePos};
+
+use crate::constants::{
+    DEBUG_ID_DIRECTIVE, DISABLE_DEBUG_ID_DIRECTIVE, DISABLE_FILE_SCOPE_DIRECTIVE, IGNORE_DIRECTIVE,
+};
+
+/// Instructions given to the plugin through comments in the source file.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Directive {
+    /// `/* @ve-debug-id name */`: use given debug id instead of the derived one.
+    DebugId(String),
+    /// `/* @ve-ignore */`: do not inject debug id.
+    Ignore,
+    /// `/* @ve-disable-debug-id */` at the top of the file: do not inject any debug id.
+    DisableDebugId,
+    /// `/* @ve-disable-file-scope */` at the top of the file: do not wrap module with file scope.
+    DisableFileScope,
+}
+
+/// Parses directives out of a comment's text. Leading `*` of JSDoc style comments are ignored.
+pub fn parse_directives(text: &str) -> Vec<Directive> {
+    let mut directives = vec![];
+    let mut tokens = text
+        .split_whitespace()
+        .map(|token| token.trim_start_matches('*'))
+        .filter(|token| !token.is_empty());
+
+    while let Some(token) = tokens.next() {
+        if token == DEBUG_ID_DIRECTIVE {
+            if let Some(debug_id) = tokens.next() {
+                directives.push(Directive::DebugId(debug_id.to_string()));
+            }
+        } else if token == IGNORE_DIRECTIVE {
+            directives.push(Directive::Ignore);
+        } else if token == DISABLE_DEBUG_ID_DIRECTIVE {
+            directives.push(Directive::DisableDebugId);
+        } else if token == DISABLE_FILE_SCOPE_DIRECTIVE {
+            directives.push(Directive::DisableFileScope);
+        }
+    }
+
+    directives
+}
+
+/// Returns all directives from the leading comments of given position.
+pub fn get_leading_directives<C: Comments>(comments: &C, pos: BytePos) -> Vec<Directive> {
+    comments
+        .get_leading(pos)
+        .map(|comments| {
+            comments
+                .iter()
+                .flat_map(|comment| parse_directives(&comment.text))
+                .collect()
+        })
+        .unwrap_or_default()
+}
@@ -0,0 +1,225 @@
+use crate::constants::CSS_FILE_FILTER_REGEX;
+
+/// Splits a slash-separated path into its root (`/`, `C:/`, `//server/share/`) and the rest.
+/// Relative paths have an empty root.
+fn split_root(path: &str) -> (&str, &str) {
+    if let Some(unc) = path.strip_prefix("//") {
+        // UNC path: `//server/share/...`. Root includes server and share name.
+        let mut end = 2;
+        for segment in unc.splitn(3, '/').take(2) {
+            end += segment.len() + 1;
+        }
+        let end = end.min(path.len());
+        return path.split_at(end);
+    }
+
+    let bytes = path.as_bytes();
+    if bytes.len() >= 2 && bytes[0].is_ascii_alphabetic() && bytes[1] == b':' {
+        // Drive path: `C:/...`, `C:...`
+        return if bytes.get(2) == Some(&b'/') {
+            path.split_at(3)
+        } else {
+            path.split_at(2)
+        };
+    }
+
+    if path.starts_with('/') {
+        return path.split_at(1);
+    }
+
+    ("", path)
+}
+
+/// Strips bundler query strings (`?used`, `?vanilla&type=css`) and fragments from the filename.
+pub fn strip_query(filename: &str) -> &str {
+    filename
+        .find(['?', '#'])
+        .map(|idx| &filename[..idx])
+        .unwrap_or(filename)
+}
+
+/// Lexically normalises given path: converts separators into forward slashes and collapses
+/// `.` / `..` segments without touching the filesystem, so it behaves identically regardless of
+/// the host (including wasm sandbox) the plugin runs on.
+pub fn normalize_path(path: &str) -> String {
+    let path = path.replace('\\', "/");
+    let (root, rest) = split_root(&path);
+    let root = if root.starts_with("//") && !root.ends_with('/') {
+        format!("{}/", root)
+    } else {
+        root.to_string()
+    };
+
+    let mut segments: Vec<&str> = vec![];
+    for segment in rest.split('/') {
+        match segment {
+            "" | "." => {}
+            ".." => match segments.last() {
+                Some(last) if *last != ".." => {
+                    segments.pop();
+                }
+                // Cannot go above the root of an absolute path
+                _ if !root.is_empty() => {}
+                _ => segments.push(".."),
+            },
+            segment => segments.push(segment),
+        }
+    }
+
+    let normalized = format!("{}{}", root, segments.join("/"));
+    if normalized.is_empty() {
+        ".".to_string()
+    } else {
+        normalized
+    }
+}
+
+pub fn is_absolute(path: &str) -> bool {
+    let path = path.replace('\\', "/");
+    let (root, _) = split_root(&path);
+    root.ends_with('/')
+}
+
+/// Resolves the filename given by SWC against the package dir, returns normalised absolute path
+/// without query or fragments.
+pub fn normalize_file_path(package_dir: &str, filename: &str) -> String {
+    let filename = strip_query(filename);
+
+    if is_absolute(filename) {
+        normalize_path(filename)
+    } else {
+        normalize_path(&format!("{}/{}", package_dir, filename))
+    }
+}
+
+/// Returns given normalised path relative to the normalised base dir, if it's under the base dir.
+pub fn relative_path<'a>(path: &'a str, base: &str) -> Option<&'a str> {
+    if base.is_empty() || base == "." {
+        return Some(path);
+    }
+
+    if base.ends_with('/') {
+        path.strip_prefix(base)
+    } else {
+        path.strip_prefix(base)
+            .and_then(|relative| relative.strip_prefix('/'))
+    }
+}
+
+/// Returns the file name of given normalised path without its extension. Style files lose the
+/// whole `.css.ts` extension, i.e `src/button.css.ts` becomes `button`.
+pub fn file_stem(path: &str) -> &str {
+    let file_name = path.rsplit('/').next().unwrap_or(path);
+
+    if let Some(found) = CSS_FILE_FILTER_REGEX.find(file_name) {
+        return &file_name[..found.start()];
+    }
+
+    match file_name.rfind('.') {
+        Some(idx) if idx > 0 => &file_name[..idx],
+        _ => file_name,
+    }
+}
+
+/// Returns the name of the directory containing given normalised path.
+pub fn dir_name(path: &str) -> &str {
+    let mut segments = path.rsplit('/').skip(1);
+    segments.next().unwrap_or_default()
+}
+
+#[cfg(test)]
+mod tests {
+    use pretty_assertions::assert_eq;
+
+    use super::*;
+
+    #[test]
+    fn strips_query_and_fragment() {
+        assert_eq!(strip_query("src/button.css.ts?used"), "src/button.css.ts");
+        assert_eq!(
+            strip_query("src/button.css.ts?vanilla&type=css"),
+            "src/button.css.ts"
+        );
+        assert_eq!(strip_query("src/button.css.ts#hash"), "src/button.css.ts");
+        assert_eq!(strip_query("src/button.css.ts"), "src/button.css.ts");
+    }
+
+    #[test]
+    fn joins_relative_filename() {
+        assert_eq!(
+            normalize_file_path("/repo", "dir/mockFilename.css.ts"),
+            "/repo/dir/mockFilename.css.ts"
+        );
+        assert_eq!(
+            normalize_file_path("/repo/", "./dir/mockFilename.css.ts?used"),
+            "/repo/dir/mockFilename.css.ts"
+        );
+    }
+
+    #[test]
+    fn collapses_dot_segments() {
+        assert_eq!(
+            normalize_file_path("/repo/packages/app", "../button/./src/button.css.ts"),
+            "/repo/packages/button/src/button.css.ts"
+        );
+        assert_eq!(normalize_path("/../repo/./a//b.css.ts"), "/repo/a/b.css.ts");
+        assert_eq!(normalize_path("../a/../../b.css.ts"), "../../b.css.ts");
+    }
+
+    #[test]
+    fn keeps_absolute_filename() {
+        assert_eq!(
+            normalize_file_path("/repo", "/other/dir/button.css.ts?vanilla&type=css"),
+            "/other/dir/button.css.ts"
+        );
+    }
+
+    #[test]
+    fn handles_windows_drive_path() {
+        assert_eq!(
+            normalize_file_path("C:\\repo", "src\\styles\\..\\button.css.ts"),
+            "C:/repo/src/button.css.ts"
+        );
+        assert_eq!(
+            normalize_file_path("C:\\repo", "D:\\other\\button.css.ts"),
+            "D:/other/button.css.ts"
+        );
+    }
+
+    #[test]
+    fn handles_unc_path() {
+        assert_eq!(
+            normalize_file_path("/repo", "\\\\server\\share\\src\\..\\button.css.ts"),
+            "//server/share/button.css.ts"
+        );
+        assert_eq!(
+            normalize_file_path("\\\\server\\share\\repo", "src\\button.css.ts"),
+            "//server/share/repo/src/button.css.ts"
+        );
+        assert_eq!(
+            normalize_path("\\\\server\\share\\..\\button.css.ts"),
+            "//server/share/button.css.ts"
+        );
+    }
+
+    #[test]
+    fn resolves_relative_path() {
+        assert_eq!(
+            relative_path("/repo/packages/button/a.css.ts", "/repo"),
+            Some("packages/button/a.css.ts")
+        );
+        assert_eq!(relative_path("/repository/a.css.ts", "/repo"), None);
+        assert_eq!(relative_path("/repo/a.css.ts", "/"), Some("repo/a.css.ts"));
+        assert_eq!(relative_path("/a.css.ts", "/other"), None);
+    }
+
+    #[test]
+    fn extracts_file_stem_and_dir() {
+        assert_eq!(file_stem("/repo/src/button.css.ts"), "button");
+        assert_eq!(file_stem("/repo/src/button.styles.css.js"), "button.styles");
+        assert_eq!(file_stem("/repo/src/button.ts"), "button");
+        assert_eq!(file_stem(".eslintrc"), ".eslintrc");
+        assert_eq!(dir_name("/repo/src/button.css.ts"), "src");
+        assert_eq!(dir_name("button.css.ts"), "");
+    }
+}
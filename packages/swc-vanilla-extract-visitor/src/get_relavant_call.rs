@@ -1,8 +1,8 @@
 use std::collections::HashMap;
 
-use swc_core::ecma::ast::{CallExpr, Callee, Expr, Ident, MemberProp};
+use swc_core::ecma::ast::{CallExpr, Callee, Expr, Id, Ident, MemberProp};
 
-use crate::constants::STYLE_FUNCTIONS;
+use crate::constants::{DEBUGGABLE_FUNCTION_CONFIG, STYLE_FUNCTIONS};
 
 pub fn get_relavant_call(
     call_expr: &CallExpr,
@@ -34,7 +34,7 @@ pub fn get_relavant_call(
                 }
             }
         }
-        return None;
+        None
     } else {
         let import_info = import_identifiers.keys().find(|ident| {
             if let Callee::Expr(expr) = callee {
@@ -48,9 +48,47 @@ pub fn get_relavant_call(
             }
         });
 
-        return import_info
-            .map(|key| import_identifiers.get(key))
-            .flatten()
-            .cloned();
+        import_info
+            .and_then(|key| import_identifiers.get(key))
+            .cloned()
+    }
+}
+
+/// Returns the argument position to inject the debug id at, if given call is a debuggable
+/// vanilla-extract call or a call to a local wrapper forwarding its debug id, and the call does not
+/// pass a debug id already.
+pub fn get_debug_id_position(
+    call_expr: &CallExpr,
+    namespace_import: &Option<Ident>,
+    import_identifiers: &HashMap<Ident, String>,
+    wrappers: &HashMap<Id, usize>,
+) -> Option<usize> {
+    if let Some(used_export) = get_relavant_call(call_expr, namespace_import, import_identifiers) {
+        return DEBUGGABLE_FUNCTION_CONFIG
+            .get(&used_export)
+            .filter(|max_params| call_expr.args.len() < **max_params)
+            .map(|_| call_expr.args.len());
+    }
+
+    if let Some(position) = get_wrapper_position(call_expr, wrappers) {
+        // Spread arguments make the position of the debug id unknown
+        let has_spread = call_expr.args.iter().any(|arg| arg.spread.is_some());
+        if call_expr.args.len() <= position && !has_spread {
+            return Some(position);
+        }
+    }
+
+    None
+}
+
+/// Returns the position of the parameter forwarded as the debug id, if given call is a call to a
+/// local wrapper. Wrappers are keyed by binding, so a local shadowing one of them is no wrapper.
+pub fn get_wrapper_position(call_expr: &CallExpr, wrappers: &HashMap<Id, usize>) -> Option<usize> {
+    match &call_expr.callee {
+        Callee::Expr(expr) => match &**expr {
+            Expr::Ident(ident) => wrappers.get(&ident.to_id()).copied(),
+            _ => None,
+        },
+        _ => None,
     }
 }
@@ -0,0 +1,85 @@
+use std::str::FromStr;
+
+use swc_core::{
+    common::DUMMY_SP,
+    ecma::ast::{Expr, Lit, ModuleItem, Stmt, Str},
+    quote,
+};
+
+/// Hot module replacement API of the dev server the module runs in.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum HmrRuntime {
+    /// `import.meta.hot`, i.e Vite.
+    Vite,
+    /// `module.hot`, i.e webpack.
+    Webpack,
+}
+
+impl FromStr for HmrRuntime {
+    type Err = String;
+
+    /// Parses the runtime from its name in plugin options, `vite` or `webpack`.
+    fn from_str(runtime: &str) -> Result<Self, Self::Err> {
+        match runtime {
+            "vite" => Ok(HmrRuntime::Vite),
+            "webpack" => Ok(HmrRuntime::Webpack),
+            _ => Err(format!("Unsupported hmr runtime: {}", runtime)),
+        }
+    }
+}
+
+/// Creates the statement handling hot updates of the module. When the runtime disposes the module,
+/// it clears the styles it injected, so rules removed from the module do not linger until a full
+/// reload. The module only accepts its own updates when `accept` is set: class names depend on the
+/// position of each style in the file, so inserting or reordering a style changes the exports, and
+/// a self-accepted update leaves importers with stale class names.
+pub fn create_hmr_handler(runtime: HmrRuntime, accept: bool, file_path: &str) -> ModuleItem {
+    // vanilla-extract's runtime tags the style element of each file scope with its file path
+    let selector = Expr::Lit(Lit::Str(Str {
+        span: DUMMY_SP,
+        value: format!("style[data-file=\"{}\"]", file_path.replace('"', "\\\"")).into(),
+        raw: None,
+    }));
+
+    let mut handler = match runtime {
+        HmrRuntime::Vite => quote!(
+            "if (import.meta.hot) {
+                import.meta.hot.dispose(() => {
+                    if (typeof document !== 'undefined') {
+                        document.querySelectorAll($selector).forEach((style) => {
+                            style.textContent = '';
+                        });
+                    }
+                });
+            }" as ModuleItem,
+            selector: Expr = selector
+        ),
+        HmrRuntime::Webpack => quote!(
+            "if (module.hot) {
+                module.hot.dispose(() => {
+                    if (typeof document !== 'undefined') {
+                        document.querySelectorAll($selector).forEach((style) => {
+                            style.textContent = '';
+                        });
+                    }
+                });
+            }" as ModuleItem,
+            selector: Expr = selector
+        ),
+    };
+
+    if accept {
+        let accept_call = match runtime {
+            HmrRuntime::Vite => quote!("import.meta.hot.accept();" as Stmt),
+            HmrRuntime::Webpack => quote!("module.hot.accept();" as Stmt),
+        };
+
+        if let ModuleItem::Stmt(Stmt::If(if_stmt)) = &mut handler {
+            if let Stmt::Block(block) = &mut *if_stmt.cons {
+                block.stmts.insert(0, accept_call);
+            }
+        }
+    }
+
+    handler
+}
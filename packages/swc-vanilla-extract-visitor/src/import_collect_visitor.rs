@@ -1,19 +1,82 @@
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
 
-use swc_core::ecma::{
-    ast::{ExportDecl, Ident, ImportDecl, ImportSpecifier, ModuleExportName},
-    visit::Visit,
+use swc_core::{
+    common::Span,
+    ecma::{
+        ast::{
+            CallExpr, Callee, Decl, ExportDecl, ExportDefaultExpr, ExportSpecifier, Expr, Id,
+            Ident, ImportDecl, ImportSpecifier, Lit, MemberProp, ModuleExportName, NamedExport,
+            ObjectPatProp, Pat, VarDeclarator,
+        },
+        atoms::JsWord,
+        visit::{Visit, VisitWith},
+    },
 };
 
-use crate::constants::{FILE_SCOPE_PACKAGE_IDENTIFIER, PACKAGE_IDENTIFIERS, STYLE_FUNCTIONS};
+use crate::{
+    constants::{
+        FILE_SCOPE_OPTIMIZED_DEP_IDENTIFIER, FILE_SCOPE_PACKAGE_IDENTIFIER, PACKAGE_IDENTIFIERS,
+        STYLE_FUNCTIONS,
+    },
+    prop_name::get_prop_name,
+    unwrap_expr::unwrap_expr,
+};
+
+const SET_FILE_SCOPE: &str = "setFileScope";
+const REQUIRE_FUNCTIONS: [&str; 2] = ["require", "__vi_ssr_import__"];
+
+/// Returns true if given module specifier resolves to `@vanilla-extract/css/fileScope`,
+/// including deep imports into its dist files and Vite's prebundled dependency.
+pub(crate) fn is_file_scope_source(src: &str) -> bool {
+    src == FILE_SCOPE_PACKAGE_IDENTIFIER
+        || src
+            .strip_prefix(FILE_SCOPE_PACKAGE_IDENTIFIER)
+            .is_some_and(|rest| rest.starts_with('/'))
+        || src.contains(FILE_SCOPE_OPTIMIZED_DEP_IDENTIFIER)
+}
+
+/// Returns true if given expression requires the file scope module, either bare
+/// (`require('@vanilla-extract/css/fileScope')`) or wrapped by an interop helper, i.e
+/// `_interop_require_wildcard(require(...))` or esbuild's `__toESM(require(...))`. Vite's SSR
+/// `await __vi_ssr_import__(...)` counts as a require.
+fn is_file_scope_require(expr: &Expr) -> bool {
+    let call_expr = match unwrap_expr(expr) {
+        Expr::Call(call_expr) => call_expr,
+        Expr::Await(await_expr) => return is_file_scope_require(&await_expr.arg),
+        _ => return false,
+    };
+    let arg = match call_expr.args.first() {
+        Some(arg) if arg.spread.is_none() => &*arg.expr,
+        _ => return false,
+    };
+
+    match (&call_expr.callee, arg) {
+        (Callee::Expr(callee), Expr::Lit(Lit::Str(src))) if matches!(&**callee, Expr::Ident(ident) if REQUIRE_FUNCTIONS.contains(&&*ident.sym)) => {
+            is_file_scope_source(&src.value)
+        }
+        _ => is_file_scope_require(arg),
+    }
+}
 
 /// A visitor to collect imports from vanilla-extract packages
 pub struct ImportCollectVisitor {
     pub is_esm: bool,
+    /// Set when the module calls `setFileScope` imported or required from the file scope module,
+    /// as vanilla-extract's own output and bundlers compiling it do.
     pub is_compiled: bool,
 
     pub namespace_import: Option<Ident>,
     pub import_identifiers: HashMap<Ident, String>,
+    /// Name each exported top level binding is exported as.
+    pub exports: HashMap<JsWord, String>,
+    /// Module specifiers imported at runtime, with the span of their import declaration.
+    pub imports: Vec<(String, Span)>,
+
+    /// Bindings of `setFileScope` imported or required from the file scope module.
+    set_file_scope_bindings: HashSet<Id>,
+    /// Bindings of the file scope module itself, i.e `import * as fileScope` or
+    /// `var _fileScope = _interop_require_wildcard(require(...))`.
+    file_scope_namespaces: HashSet<Id>,
 }
 
 impl ImportCollectVisitor {
@@ -24,6 +87,37 @@ impl ImportCollectVisitor {
 
             namespace_import: None,
             import_identifiers: Default::default(),
+            exports: Default::default(),
+            imports: vec![],
+
+            set_file_scope_bindings: Default::default(),
+            file_scope_namespaces: Default::default(),
+        }
+    }
+
+    /// Returns true if given callee is `setFileScope` of the file scope module, either imported by
+    /// name, through the module (`fileScope.setFileScope`) or through the indirect call
+    /// transpilers emit (`(0, _fileScope.setFileScope)`). Functions merely named `setFileScope`
+    /// are not.
+    fn is_set_file_scope_callee(&self, expr: &Expr) -> bool {
+        match unwrap_expr(expr) {
+            Expr::Ident(ident) => self.set_file_scope_bindings.contains(&ident.to_id()),
+            Expr::Member(member_expr) => {
+                let is_set_file_scope = matches!(
+                    &member_expr.prop,
+                    MemberProp::Ident(ident) if &*ident.sym == SET_FILE_SCOPE
+                );
+                let is_file_scope_module = match unwrap_expr(&member_expr.obj) {
+                    Expr::Ident(ident) => self.file_scope_namespaces.contains(&ident.to_id()),
+                    obj => is_file_scope_require(obj),
+                };
+                is_set_file_scope && is_file_scope_module
+            }
+            Expr::Seq(seq_expr) => seq_expr
+                .exprs
+                .last()
+                .is_some_and(|expr| self.is_set_file_scope_callee(expr)),
+            _ => false,
         }
     }
 }
@@ -32,16 +126,42 @@ impl Visit for ImportCollectVisitor {
     fn visit_import_decl(&mut self, import_decl: &ImportDecl) {
         self.is_esm = true;
 
+        if !import_decl.type_only {
+            self.imports
+                .push((import_decl.src.value.to_string(), import_decl.span));
+        }
+
         if self.is_compiled {
             // Bail early if file isn't a .css.ts file or the file has already been compiled
             return;
         }
 
         let src = &*import_decl.src.value;
-        if src == FILE_SCOPE_PACKAGE_IDENTIFIER {
-            // If file scope import is found it means the file has already been compiled
-            self.is_compiled = true;
-            return;
+        if is_file_scope_source(src) {
+            // The file has already been compiled once it calls `setFileScope` of the import
+            for specifier in &import_decl.specifiers {
+                match specifier {
+                    ImportSpecifier::Named(named_specifier) => {
+                        let import_name = match &named_specifier.imported {
+                            Some(ModuleExportName::Ident(ident)) => &*ident.sym,
+                            Some(ModuleExportName::Str(str)) => &*str.value,
+                            None => &*named_specifier.local.sym,
+                        };
+                        if import_name == SET_FILE_SCOPE {
+                            self.set_file_scope_bindings
+                                .insert(named_specifier.local.to_id());
+                        }
+                    }
+                    ImportSpecifier::Default(default_specifier) => {
+                        self.file_scope_namespaces
+                            .insert(default_specifier.local.to_id());
+                    }
+                    ImportSpecifier::Namespace(namespace_specifier) => {
+                        self.file_scope_namespaces
+                            .insert(namespace_specifier.local.to_id());
+                    }
+                }
+            }
         } else if PACKAGE_IDENTIFIERS.contains(src) {
             for specifier in &import_decl.specifiers {
                 match specifier {
@@ -75,7 +195,129 @@ impl Visit for ImportCollectVisitor {
         }
     }
 
-    fn visit_export_decl(&mut self, _: &ExportDecl) {
+    fn visit_export_decl(&mut self, export_decl: &ExportDecl) {
         self.is_esm = true;
+
+        match &export_decl.decl {
+            Decl::Var(var_decl) => {
+                for declarator in &var_decl.decls {
+                    let idents = match &declarator.name {
+                        Pat::Ident(ident) => vec![ident],
+                        // `export const [themeClass, vars] = createTheme()`
+                        Pat::Array(array) => array
+                            .elems
+                            .iter()
+                            .filter_map(|elem| match elem {
+                                Some(Pat::Ident(ident)) => Some(ident),
+                                _ => None,
+                            })
+                            .collect(),
+                        _ => vec![],
+                    };
+                    for ident in idents {
+                        self.exports
+                            .insert(ident.id.sym.clone(), ident.id.sym.to_string());
+                    }
+                }
+            }
+            Decl::Fn(fn_decl) => {
+                self.exports
+                    .insert(fn_decl.ident.sym.clone(), fn_decl.ident.sym.to_string());
+            }
+            Decl::Class(class_decl) => {
+                self.exports.insert(
+                    class_decl.ident.sym.clone(),
+                    class_decl.ident.sym.to_string(),
+                );
+            }
+            _ => {}
+        }
+
+        export_decl.visit_children_with(self);
+    }
+
+    fn visit_named_export(&mut self, named_export: &NamedExport) {
+        self.is_esm = true;
+
+        // Re-exports from other modules do not export bindings of this file
+        if named_export.src.is_some() {
+            return;
+        }
+
+        for specifier in &named_export.specifiers {
+            if let ExportSpecifier::Named(named_specifier) = specifier {
+                if let ModuleExportName::Ident(orig) = &named_specifier.orig {
+                    let exported = match &named_specifier.exported {
+                        Some(ModuleExportName::Ident(ident)) => ident.sym.to_string(),
+                        Some(ModuleExportName::Str(str)) => str.value.to_string(),
+                        None => orig.sym.to_string(),
+                    };
+                    self.exports.entry(orig.sym.clone()).or_insert(exported);
+                }
+            }
+        }
+    }
+
+    fn visit_export_default_expr(&mut self, export_default_expr: &ExportDefaultExpr) {
+        self.is_esm = true;
+
+        if let Expr::Ident(ident) = &*export_default_expr.expr {
+            self.exports
+                .entry(ident.sym.clone())
+                .or_insert_with(|| "default".to_string());
+        }
+
+        export_default_expr.visit_children_with(self);
+    }
+
+    fn visit_var_declarator(&mut self, var_declarator: &VarDeclarator) {
+        if let Some(init) = &var_declarator.init {
+            if is_file_scope_require(init) {
+                match &var_declarator.name {
+                    Pat::Ident(ident) => {
+                        self.file_scope_namespaces.insert(ident.id.to_id());
+                    }
+                    // `const { setFileScope, endFileScope } = require(...)`
+                    Pat::Object(object_pat) => {
+                        for prop in &object_pat.props {
+                            match prop {
+                                ObjectPatProp::Assign(assign_prop)
+                                    if &*assign_prop.key.sym == SET_FILE_SCOPE =>
+                                {
+                                    self.set_file_scope_bindings.insert(assign_prop.key.to_id());
+                                }
+                                ObjectPatProp::KeyValue(key_value_prop)
+                                    if get_prop_name(&key_value_prop.key).as_deref()
+                                        == Some(SET_FILE_SCOPE) =>
+                                {
+                                    if let Pat::Ident(ident) = &*key_value_prop.value {
+                                        self.set_file_scope_bindings.insert(ident.id.to_id());
+                                    }
+                                }
+                                _ => {}
+                            }
+                        }
+                    }
+                    _ => {}
+                }
+            }
+        }
+
+        var_declarator.visit_children_with(self);
+    }
+
+    fn visit_call_expr(&mut self, call_expr: &CallExpr) {
+        if self.is_compiled {
+            return;
+        }
+
+        if let Callee::Expr(callee) = &call_expr.callee {
+            if self.is_set_file_scope_callee(callee) {
+                self.is_compiled = true;
+                return;
+            }
+        }
+
+        call_expr.visit_children_with(self);
     }
 }
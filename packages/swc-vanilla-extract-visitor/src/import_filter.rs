@@ -0,0 +1,96 @@
+use crate::path_matcher::PathMatcher;
+
+/// Patterns denied by default: components, which pull their whole dependency tree into the
+/// evaluation of the style file.
+static DEFAULT_DENIED_IMPORTS: [&str; 1] = ["**/*.tsx"];
+
+/// Allow and deny lists of module specifiers style files may import. Style files are evaluated at
+/// build time, so importing components or large modules slows the build down or breaks it.
+///
+/// Patterns are matched against the specifier as written, i.e `./Button.tsx` or `lodash`, with the
+/// same syntax as [PackageMap](crate::PackageMap) patterns. Specifiers matching an allow pattern
+/// are never denied.
+#[derive(Debug, Clone)]
+pub struct ImportFilter {
+    allowed: Vec<PathMatcher>,
+    denied: Vec<(String, PathMatcher)>,
+}
+
+impl Default for ImportFilter {
+    /// Denies `.tsx` imports.
+    fn default() -> Self {
+        let mut import_filter = ImportFilter::new();
+        for pattern in DEFAULT_DENIED_IMPORTS {
+            import_filter.deny(pattern);
+        }
+        import_filter
+    }
+}
+
+impl ImportFilter {
+    /// Creates a filter which does not deny any import.
+    pub fn new() -> Self {
+        ImportFilter {
+            allowed: vec![],
+            denied: vec![],
+        }
+    }
+
+    pub fn allow(&mut self, pattern: &str) {
+        self.allowed.push(PathMatcher::new(pattern));
+    }
+
+    pub fn deny(&mut self, pattern: &str) {
+        self.denied
+            .push((pattern.to_string(), PathMatcher::new(pattern)));
+    }
+
+    /// Returns the pattern denying given specifier, if any and no allow pattern matches it.
+    pub fn denied_by(&self, specifier: &str) -> Option<&str> {
+        let specifier = specifier.trim_start_matches("./");
+        if self
+            .allowed
+            .iter()
+            .any(|matcher| matcher.is_match(specifier))
+        {
+            return None;
+        }
+
+        self.denied
+            .iter()
+            .find(|(_, matcher)| matcher.is_match(specifier))
+            .map(|(pattern, _)| pattern.as_str())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use pretty_assertions::assert_eq;
+
+    use super::*;
+
+    #[test]
+    fn denies_components_by_default() {
+        let import_filter = ImportFilter::default();
+
+        assert_eq!(import_filter.denied_by("./Button.tsx"), Some("**/*.tsx"));
+        assert_eq!(
+            import_filter.denied_by("../components/Button.tsx"),
+            Some("**/*.tsx")
+        );
+        assert_eq!(import_filter.denied_by("./theme.css.ts"), None);
+        assert_eq!(import_filter.denied_by("react"), None);
+    }
+
+    #[test]
+    fn allows_over_denies() {
+        let mut import_filter = ImportFilter::default();
+        import_filter.deny("lodash");
+        import_filter.allow("**/icons/*.tsx");
+
+        assert_eq!(import_filter.denied_by("lodash/get"), Some("lodash"));
+        assert_eq!(import_filter.denied_by("lodash-es"), None);
+        assert_eq!(import_filter.denied_by("./icons/Arrow.tsx"), None);
+        assert_eq!(import_filter.denied_by("./Button.tsx"), Some("**/*.tsx"));
+    }
+}
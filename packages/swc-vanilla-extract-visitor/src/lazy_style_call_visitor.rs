@@ -0,0 +1,618 @@
+use std::collections::HashSet;
+
+use swc_core::{
+    common::{pass::AstNodePath, BytePos, Span, Spanned},
+    ecma::{
+        ast::{
+            AssignPatProp, BindingIdent, CallExpr, Callee, ClassDecl, ClassExpr, Decl, DefaultDecl,
+            Expr, FnDecl, FnExpr, Ident, ImportSpecifier, MemberProp, ModuleDecl, ModuleItem, Pat,
+            Stmt, TsEnumDecl, TsImportEqualsDecl, TsModuleDecl, TsModuleName,
+        },
+        atoms::JsWord,
+        visit::{
+            fields::{CallExprField, NewExprField, VarDeclaratorField},
+            AstParentNodeRef, Visit, VisitAstPath, VisitWith, VisitWithPath,
+        },
+    },
+};
+
+use crate::{
+    analysis::ModuleAnalysis, get_relavant_call::get_relavant_call,
+    reference_collect_visitor::ReferenceCollectVisitor,
+};
+
+/// Functions and methods known to call the callbacks given to them later, not while the file is
+/// evaluated.
+static DEFERRING_CALLEES: [&str; 9] = [
+    "setTimeout",
+    "setInterval",
+    "requestAnimationFrame",
+    "requestIdleCallback",
+    "queueMicrotask",
+    "addEventListener",
+    "then",
+    "catch",
+    "finally",
+];
+
+/// Returns the name of the function or method called, i.e `then` for `promise.then()`.
+fn get_callee_name(callee: &Callee) -> Option<&JsWord> {
+    match callee {
+        Callee::Expr(expr) => match &**expr {
+            Expr::Ident(ident) => Some(&ident.sym),
+            Expr::Member(member_expr) => match &member_expr.prop {
+                MemberProp::Ident(ident) => Some(&ident.sym),
+                _ => None,
+            },
+            _ => None,
+        },
+        _ => None,
+    }
+}
+
+/// Returns true if given node only wraps the function below it, i.e parentheses or type
+/// assertions.
+fn is_transparent(node: &AstParentNodeRef<'_>) -> bool {
+    matches!(
+        node,
+        AstParentNodeRef::Expr(..)
+            | AstParentNodeRef::ParenExpr(..)
+            | AstParentNodeRef::FnExpr(..)
+            | AstParentNodeRef::Callee(..)
+            | AstParentNodeRef::TsAsExpr(..)
+            | AstParentNodeRef::TsConstAssertion(..)
+            | AstParentNodeRef::TsNonNullExpr(..)
+            | AstParentNodeRef::TsTypeAssertion(..)
+    )
+}
+
+/// Collects the names of the bindings declared in a node, including nested scopes.
+#[derive(Default)]
+struct BindingCollectVisitor {
+    bindings: HashSet<JsWord>,
+}
+
+impl Visit for BindingCollectVisitor {
+    fn visit_binding_ident(&mut self, binding_ident: &BindingIdent) {
+        self.bindings.insert(binding_ident.id.sym.clone());
+    }
+
+    fn visit_assign_pat_prop(&mut self, assign_pat_prop: &AssignPatProp) {
+        // `const { color = 'red' } = tokens`
+        self.bindings.insert(assign_pat_prop.key.sym.clone());
+        assign_pat_prop.visit_children_with(self);
+    }
+
+    fn visit_fn_decl(&mut self, fn_decl: &FnDecl) {
+        self.bindings.insert(fn_decl.ident.sym.clone());
+        fn_decl.visit_children_with(self);
+    }
+
+    fn visit_fn_expr(&mut self, fn_expr: &FnExpr) {
+        if let Some(ident) = &fn_expr.ident {
+            self.bindings.insert(ident.sym.clone());
+        }
+        fn_expr.visit_children_with(self);
+    }
+
+    fn visit_class_decl(&mut self, class_decl: &ClassDecl) {
+        self.bindings.insert(class_decl.ident.sym.clone());
+        class_decl.visit_children_with(self);
+    }
+
+    fn visit_class_expr(&mut self, class_expr: &ClassExpr) {
+        if let Some(ident) = &class_expr.ident {
+            self.bindings.insert(ident.sym.clone());
+        }
+        class_expr.visit_children_with(self);
+    }
+
+    fn visit_ts_enum_decl(&mut self, ts_enum_decl: &TsEnumDecl) {
+        self.bindings.insert(ts_enum_decl.id.sym.clone());
+        ts_enum_decl.visit_children_with(self);
+    }
+
+    fn visit_ts_module_decl(&mut self, ts_module_decl: &TsModuleDecl) {
+        // `declare module 'name'` does not declare a binding
+        if let TsModuleName::Ident(ident) = &ts_module_decl.id {
+            self.bindings.insert(ident.sym.clone());
+        }
+        ts_module_decl.visit_children_with(self);
+    }
+
+    fn visit_ts_import_equals_decl(&mut self, ts_import_equals_decl: &TsImportEqualsDecl) {
+        // `import color = Theme.color`
+        self.bindings.insert(ts_import_equals_decl.id.sym.clone());
+    }
+
+    fn visit_import_specifier(&mut self, import_specifier: &ImportSpecifier) {
+        let local = match import_specifier {
+            ImportSpecifier::Named(specifier) => &specifier.local,
+            ImportSpecifier::Default(specifier) => &specifier.local,
+            ImportSpecifier::Namespace(specifier) => &specifier.local,
+        };
+        self.bindings.insert(local.sym.clone());
+    }
+}
+
+/// Bindings of a top level item, to tell whether a call in it can be hoisted before it.
+struct ItemBindings {
+    pos: BytePos,
+    /// Bindings declared anywhere in the item, including nested scopes.
+    declared: HashSet<JsWord>,
+    /// Bindings the item declares at top level.
+    top_level: HashSet<JsWord>,
+}
+
+impl ItemBindings {
+    fn new(item: &ModuleItem) -> Self {
+        let mut declared = BindingCollectVisitor::default();
+        item.visit_with(&mut declared);
+
+        let decl = match item {
+            ModuleItem::Stmt(Stmt::Decl(decl)) => Some(decl),
+            ModuleItem::ModuleDecl(ModuleDecl::ExportDecl(export_decl)) => Some(&export_decl.decl),
+            _ => None,
+        };
+        let mut top_level = BindingCollectVisitor::default();
+        match (item, decl) {
+            (ModuleItem::ModuleDecl(ModuleDecl::Import(import_decl)), _) => {
+                import_decl.visit_with(&mut top_level)
+            }
+            (ModuleItem::ModuleDecl(ModuleDecl::TsImportEquals(ts_import_equals_decl)), _) => {
+                top_level
+                    .bindings
+                    .insert(ts_import_equals_decl.id.sym.clone());
+            }
+            (ModuleItem::ModuleDecl(ModuleDecl::ExportDefaultDecl(export_default_decl)), _) => {
+                let ident = match &export_default_decl.decl {
+                    DefaultDecl::Fn(fn_expr) => fn_expr.ident.as_ref(),
+                    DefaultDecl::Class(class_expr) => class_expr.ident.as_ref(),
+                    DefaultDecl::TsInterfaceDecl(..) => None,
+                };
+                if let Some(ident) = ident {
+                    top_level.bindings.insert(ident.sym.clone());
+                }
+            }
+            (_, Some(Decl::Var(var_decl))) => {
+                for declarator in &var_decl.decls {
+                    declarator.name.visit_with(&mut top_level);
+                }
+            }
+            (_, Some(Decl::Fn(fn_decl))) => {
+                top_level.bindings.insert(fn_decl.ident.sym.clone());
+            }
+            (_, Some(Decl::Class(class_decl))) => {
+                top_level.bindings.insert(class_decl.ident.sym.clone());
+            }
+            (_, Some(Decl::TsEnum(ts_enum_decl))) => {
+                top_level.bindings.insert(ts_enum_decl.id.sym.clone());
+            }
+            (_, Some(Decl::TsModule(ts_module_decl))) => {
+                if let TsModuleName::Ident(ident) = &ts_module_decl.id {
+                    top_level.bindings.insert(ident.sym.clone());
+                }
+            }
+            _ => {}
+        }
+
+        ItemBindings {
+            pos: item.span_lo(),
+            declared: declared.bindings,
+            top_level: top_level.bindings,
+        }
+    }
+}
+
+/// Top level declaration a lazy style call can be moved to.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Hoist {
+    /// Position of the top level item containing the call, to declare the binding before.
+    pub pos: BytePos,
+    /// Name of the binding, unused in the module.
+    pub name: String,
+}
+
+/// A style call which does not run while the file is evaluated.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct LazyStyleCall {
+    /// Function called, i.e `style`.
+    pub used_export: String,
+    pub span: Span,
+    /// Declaration to move the call to, if it does not depend on the function it's called in.
+    pub hoist: Option<Hoist>,
+}
+
+/// Sorts calls to style functions by whether they run while the file is evaluated. Calls which do
+/// not, i.e in event handlers or in functions which are never called, escape the file scope.
+///
+/// Functions are considered to run while the file is evaluated if they are immediately invoked,
+/// given as a callback to a call which runs while the file is evaluated (unless the callee is known
+/// to defer it, like `setTimeout`), or called by name from code which runs while the file is
+/// evaluated. In style files, exported functions are considered to run as well, since style files
+/// importing them call them in their own file scope. Whether the export itself can be serialised
+/// is up to `NonSerializableExports`, which only warns as the file may only be imported by style
+/// files.
+struct LazyStyleCallVisitor<'a> {
+    analysis: &'a ModuleAnalysis,
+    /// True if exported functions are considered to run while the file is evaluated.
+    runs_exports: bool,
+    /// Local functions called while the file is evaluated.
+    evaluated_functions: HashSet<JsWord>,
+    /// True if a function has been added to `evaluated_functions` during the pass.
+    has_changed: bool,
+    items: Vec<ItemBindings>,
+    item_index: usize,
+    /// Bindings declared anywhere in the module, to tell globals apart.
+    declared: HashSet<JsWord>,
+
+    /// Lazy calls with the position of the top level item to hoist them before, if possible.
+    lazy_calls: Vec<(String, Span, Option<BytePos>)>,
+    /// Calls which run while the file is evaluated.
+    evaluated_calls: Vec<(String, Span)>,
+}
+
+impl<'a> LazyStyleCallVisitor<'a> {
+    fn new(items: &[ModuleItem], analysis: &'a ModuleAnalysis, runs_exports: bool) -> Self {
+        let item_bindings: Vec<_> = items.iter().map(ItemBindings::new).collect();
+        let declared = item_bindings
+            .iter()
+            .flat_map(|item| item.declared.iter().cloned())
+            .collect();
+
+        LazyStyleCallVisitor {
+            analysis,
+            runs_exports,
+            evaluated_functions: Default::default(),
+            has_changed: false,
+            items: item_bindings,
+            item_index: 0,
+            declared,
+            lazy_calls: vec![],
+            evaluated_calls: vec![],
+        }
+    }
+
+    /// Visits given items, until no new function is found to run as functions may be called by
+    /// other functions declared later in the file.
+    fn visit_items(&mut self, items: &[ModuleItem]) {
+        loop {
+            self.has_changed = false;
+            self.lazy_calls.clear();
+            self.evaluated_calls.clear();
+            for (item_index, item) in items.iter().enumerate() {
+                self.item_index = item_index;
+                item.visit_with_path(self, &mut Default::default());
+            }
+            if !self.has_changed {
+                break;
+            }
+        }
+    }
+
+    fn is_evaluated_function(&self, name: &JsWord) -> bool {
+        self.evaluated_functions.contains(name)
+            || (self.runs_exports && self.analysis.exports.contains_key(name))
+    }
+
+    /// Returns true if the node at the end of given path runs while the file is evaluated.
+    fn is_evaluated(&self, ast_path: &AstNodePath<AstParentNodeRef<'_>>) -> bool {
+        let nodes: Vec<_> = ast_path.iter().collect();
+
+        let mut index = nodes.len();
+        while index > 0 {
+            index -= 1;
+            match nodes[index] {
+                AstParentNodeRef::Function(function, _) => {
+                    if function.is_async || function.is_generator {
+                        return false;
+                    }
+                }
+                AstParentNodeRef::ArrowExpr(arrow_expr, _) => {
+                    if arrow_expr.is_async || arrow_expr.is_generator {
+                        return false;
+                    }
+                }
+                AstParentNodeRef::Constructor(..)
+                | AstParentNodeRef::GetterProp(..)
+                | AstParentNodeRef::SetterProp(..) => return false,
+                _ => continue,
+            }
+
+            // Found the innermost function, check how it's used
+            while index > 0 && is_transparent(nodes[index - 1]) {
+                index -= 1;
+            }
+            if index == 0 {
+                return false;
+            }
+            index -= 1;
+
+            match nodes[index] {
+                // `(() => style())()`
+                AstParentNodeRef::CallExpr(_, CallExprField::Callee) => {}
+                // `items.map(() => style())`
+                AstParentNodeRef::ExprOrSpread(..) if index > 0 => match nodes[index - 1] {
+                    AstParentNodeRef::CallExpr(call_expr, CallExprField::Args(..)) => {
+                        let is_deferred = get_callee_name(&call_expr.callee)
+                            .is_some_and(|name| DEFERRING_CALLEES.contains(&&**name));
+                        if is_deferred {
+                            return false;
+                        }
+                        index -= 1;
+                    }
+                    AstParentNodeRef::NewExpr(_, NewExprField::Args) => {
+                        index -= 1;
+                    }
+                    _ => return false,
+                },
+                // `const createStyle = () => style()`
+                AstParentNodeRef::VarDeclarator(declarator, VarDeclaratorField::Init) => {
+                    return match &declarator.name {
+                        Pat::Ident(ident) => self.is_evaluated_function(&ident.id.sym),
+                        _ => false,
+                    };
+                }
+                // `function createStyle() { return style() }`
+                AstParentNodeRef::FnDecl(fn_decl, _) => {
+                    return self.is_evaluated_function(&fn_decl.ident.sym);
+                }
+                // `export default function () { return style() }`
+                AstParentNodeRef::DefaultDecl(..) | AstParentNodeRef::ExportDefaultExpr(..) => {
+                    return self.runs_exports;
+                }
+                _ => return false,
+            }
+        }
+
+        true
+    }
+
+    /// Returns the position of the top level item containing given call, if the call can be moved
+    /// before it. Calls only referring to bindings declared at top level before the item, or to
+    /// globals, can be moved. Calls referring to function parameters or block scoped bindings are
+    /// not, even if a top level binding has the same name. Calls whose result is unused are not
+    /// either, they would leave a dangling binding behind.
+    fn get_hoist_position(
+        &self,
+        call_expr: &CallExpr,
+        ast_path: &AstNodePath<AstParentNodeRef<'_>>,
+    ) -> Option<BytePos> {
+        let parent = ast_path.iter().rev().find(|node| {
+            !matches!(
+                node,
+                AstParentNodeRef::Expr(..) | AstParentNodeRef::ParenExpr(..)
+            )
+        });
+        if matches!(parent, Some(AstParentNodeRef::ExprStmt(..))) {
+            return None;
+        }
+
+        let mut reference_collect_visitor = ReferenceCollectVisitor::default();
+        call_expr.visit_with(&mut reference_collect_visitor);
+        if reference_collect_visitor.is_contextual {
+            return None;
+        }
+
+        let item = self.items.get(self.item_index)?;
+        let earlier_items = &self.items[..self.item_index];
+        let is_hoistable = reference_collect_visitor
+            .references
+            .keys()
+            .all(|reference| {
+                let is_global = !self.declared.contains(reference);
+                let is_earlier_top_level = earlier_items
+                    .iter()
+                    .any(|earlier_item| earlier_item.top_level.contains(reference));
+                !item.declared.contains(reference) && (is_global || is_earlier_top_level)
+            });
+
+        is_hoistable.then_some(item.pos)
+    }
+}
+
+impl<'a> VisitAstPath for LazyStyleCallVisitor<'a> {
+    fn visit_call_expr<'ast: 'r, 'r>(
+        &mut self,
+        call_expr: &'r CallExpr,
+        ast_path: &mut AstNodePath<AstParentNodeRef<'r>>,
+    ) {
+        if let Some(used_export) = get_relavant_call(
+            call_expr,
+            &self.analysis.namespace_import,
+            &self.analysis.import_identifiers,
+        ) {
+            if self.is_evaluated(ast_path) {
+                self.evaluated_calls.push((used_export, call_expr.span));
+            } else {
+                let hoist_position = self.get_hoist_position(call_expr, ast_path);
+                self.lazy_calls
+                    .push((used_export, call_expr.span, hoist_position));
+            }
+        } else if let Callee::Expr(expr) = &call_expr.callee {
+            if let Expr::Ident(Ident { sym, .. }) = &**expr {
+                if !self.evaluated_functions.contains(sym) && self.is_evaluated(ast_path) {
+                    self.evaluated_functions.insert(sym.clone());
+                    self.has_changed = true;
+                }
+            }
+        }
+
+        call_expr.visit_children_with_path(self, ast_path);
+    }
+}
+
+/// Returns calls to style functions which run while a file which is not a style file is evaluated,
+/// with the function called. Calls in exported functions are left out, as style files importing
+/// them call them in their own file scope.
+pub fn find_evaluated_style_calls(
+    items: &[ModuleItem],
+    analysis: &ModuleAnalysis,
+) -> Vec<(String, Span)> {
+    let mut visitor = LazyStyleCallVisitor::new(items, analysis, false);
+    visitor.visit_items(items);
+
+    visitor.evaluated_calls
+}
+
+/// Returns calls to style functions which do not run while the file is evaluated.
+pub fn find_lazy_style_calls(
+    items: &[ModuleItem],
+    analysis: &ModuleAnalysis,
+) -> Vec<LazyStyleCall> {
+    let mut visitor = LazyStyleCallVisitor::new(items, analysis, true);
+    visitor.visit_items(items);
+
+    // Hoisted bindings are named after the function called, i.e `hoistedStyle`, avoiding any name
+    // declared or referred to in the module
+    let mut names = HashSet::new();
+    for item in items {
+        item.visit_with(&mut IdentCollectVisitor(&mut names));
+    }
+    visitor
+        .lazy_calls
+        .into_iter()
+        .map(|(used_export, span, hoist_position)| {
+            let hoist = hoist_position.map(|pos| {
+                let mut chars = used_export.chars();
+                let base_name = format!(
+                    "hoisted{}{}",
+                    chars
+                        .next()
+                        .map(|c| c.to_ascii_uppercase())
+                        .unwrap_or_default(),
+                    chars.as_str()
+                );
+                let name = (1..)
+                    .map(|index| match index {
+                        1 => base_name.clone(),
+                        index => format!("{}{}", base_name, index),
+                    })
+                    .find(|name| !names.contains(&JsWord::from(name.as_str())))
+                    .expect("Names should not be exhausted");
+                names.insert(JsWord::from(name.as_str()));
+                Hoist { pos, name }
+            });
+
+            LazyStyleCall {
+                used_export,
+                span,
+                hoist,
+            }
+        })
+        .collect()
+}
+
+/// Collects every identifier of the module.
+struct IdentCollectVisitor<'a>(&'a mut HashSet<JsWord>);
+
+impl<'a> Visit for IdentCollectVisitor<'a> {
+    fn visit_ident(&mut self, ident: &Ident) {
+        self.0.insert(ident.sym.clone());
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use pretty_assertions::assert_eq;
+    use swc_core::quote;
+
+    use super::*;
+
+    fn find(items: Vec<ModuleItem>) -> Vec<String> {
+        let analysis = ModuleAnalysis::analyze(&items);
+        find_lazy_style_calls(&items, &analysis)
+            .into_iter()
+            .map(|lazy_call| lazy_call.used_export)
+            .collect()
+    }
+
+    #[test]
+    fn allows_evaluated_calls() {
+        let items = vec![
+            quote!("import { style, globalStyle } from '@vanilla-extract/css';" as ModuleItem),
+            quote!("const card = (rule, id) => style(rule, id);" as ModuleItem),
+            quote!("const panel = function (rule) { return card(rule); };" as ModuleItem),
+            quote!("const root = panel({});" as ModuleItem),
+            quote!("const list = ['a'].map((color) => style({ color }));" as ModuleItem),
+            quote!("(() => { globalStyle('html', {}); })();" as ModuleItem),
+            quote!("export const createStyle = () => style({});" as ModuleItem),
+        ];
+
+        assert_eq!(find(items), Vec::<String>::new());
+    }
+
+    #[test]
+    fn finds_lazy_calls() {
+        let items = vec![
+            quote!("import { style, globalStyle } from '@vanilla-extract/css';" as ModuleItem),
+            quote!("const unused = () => style({});" as ModuleItem),
+            quote!("const later = () => style({});" as ModuleItem),
+            quote!("const lazy = function () { later(); };" as ModuleItem),
+            quote!("setTimeout(() => globalStyle('html', {}));" as ModuleItem),
+            quote!("const styles = { get root() { return style({}); } };" as ModuleItem),
+            quote!("(async () => { await ready; style({}); })();" as ModuleItem),
+        ];
+
+        assert_eq!(
+            find(items),
+            vec!["style", "style", "globalStyle", "style", "style"]
+        );
+    }
+
+    #[test]
+    fn hoists_independent_calls() {
+        let items = vec![
+            quote!("import { style, globalStyle } from '@vanilla-extract/css';" as ModuleItem),
+            quote!("const base = style({});" as ModuleItem),
+            quote!("const hoistedStyle = 1;" as ModuleItem),
+            quote!("const a = () => style([base, { width: Math.max(1, 2) }]);" as ModuleItem),
+            quote!("const b = () => style({ color: brand });" as ModuleItem),
+            quote!("const c = (color) => style({ color });" as ModuleItem),
+            quote!("const d = function () { return style(this.rule); };" as ModuleItem),
+            quote!("const e = () => { globalStyle('html', {}); };" as ModuleItem),
+            quote!("const f = () => style({});" as ModuleItem),
+            quote!("const brand = 'red';" as ModuleItem),
+        ];
+        let analysis = ModuleAnalysis::analyze(&items);
+
+        assert_eq!(
+            find_lazy_style_calls(&items, &analysis)
+                .into_iter()
+                .map(|lazy_call| lazy_call.hoist.map(|hoist| hoist.name))
+                .collect::<Vec<_>>(),
+            vec![
+                Some("hoistedStyle2".to_string()),
+                None,
+                None,
+                None,
+                None,
+                Some("hoistedStyle3".to_string()),
+            ]
+        );
+    }
+
+    #[test]
+    fn does_not_hoist_calls_referring_to_local_bindings() {
+        let items = vec![
+            quote!("import { style } from '@vanilla-extract/css';" as ModuleItem),
+            quote!("const color = 'red';" as ModuleItem),
+            quote!("const init = () => { var local = 'blue'; };" as ModuleItem),
+            quote!("const a = (color) => style({ color });" as ModuleItem),
+            quote!(
+                "const b = () => { const color = 'blue'; return style({ color }); };" as ModuleItem
+            ),
+            quote!("const c = () => style({ color: local });" as ModuleItem),
+            quote!("const d = () => style({ color });" as ModuleItem),
+        ];
+        let analysis = ModuleAnalysis::analyze(&items);
+
+        assert_eq!(
+            find_lazy_style_calls(&items, &analysis)
+                .into_iter()
+                .map(|lazy_call| lazy_call.hoist.map(|hoist| hoist.name))
+                .collect::<Vec<_>>(),
+            vec![None, None, None, Some("hoistedStyle".to_string())]
+        );
+    }
+}
@@ -1,12 +1,31 @@
-use constants::{CSS_FILE_FILTER_REGEX, FILE_SCOPE_IMPORT_NAME, FILE_SCOPE_PACKAGE_IDENTIFIER};
-use debug_id_find_visitor::DebugIdFindVisitor;
+use analysis::ModuleAnalysis;
+use constants::{
+    CSS_FILE_FILTER_REGEX, FILE_SCOPE_IMPORT_NAME, FILE_SCOPE_MARKER_COMMENT,
+    FILE_SCOPE_PACKAGE_IDENTIFIER,
+};
+use debug_id_find_visitor::{DebugIdCandidate, DebugIdFindVisitor};
 use debug_id_inject_visitor::DebugIdInjectVisitor;
-use import_collect_visitor::ImportCollectVisitor;
-use path_slash::PathBufExt as _;
-use std::path::PathBuf;
+use debug_id_registry::DebugIdRegistry;
+use debug_id_template::DebugIdTemplateContext;
+use diagnostics::emit;
+use directives::{get_leading_directives, Directive};
+use file_path::{
+    dir_name, file_stem, normalize_file_path, normalize_path, relative_path, strip_query,
+};
+use hmr::create_hmr_handler;
+use lazy_style_call_visitor::{find_evaluated_style_calls, find_lazy_style_calls};
+use reference_collect_visitor::ReferenceCollectVisitor;
+use serializable_exports::find_non_serializable_exports;
+use std::{collections::HashMap, sync::Arc};
+use style_rule_validator::validate_style_rules;
+use theme_contract_validator::find_theme_contract_mismatches;
+use unused_style_visitor::find_unused_styles;
 
 use swc_core::{
-    common::{comments::Comments, SourceMapper, DUMMY_SP},
+    common::{
+        comments::{Comment, CommentKind, Comments, NoopComments},
+        SourceMap, SourceMapper, Span, Spanned, DUMMY_SP,
+    },
     ecma::{
         ast::{
             CallExpr, Callee, Expr, ExprOrSpread, ExprStmt, Ident, ImportDecl, ImportSpecifier,
@@ -18,140 +37,510 @@ use swc_core::{
     quote,
 };
 
+pub mod analysis;
+mod config;
 mod constants;
+mod css_properties;
 mod debug_id_find_visitor;
 mod debug_id_inject_visitor;
+mod debug_id_registry;
+mod debug_id_sanitizer;
+mod debug_id_template;
+mod diagnostics;
+mod directives;
+mod file_path;
 mod get_relavant_call;
+mod hmr;
 mod import_collect_visitor;
+mod import_filter;
+mod lazy_style_call_visitor;
+mod package_map;
+mod path_matcher;
+mod prop_name;
+mod reference_collect_visitor;
+mod report;
+mod selector;
+mod serializable_exports;
+mod style_rule_validator;
+mod theme_contract_validator;
+mod unused_style_visitor;
+mod unwrap_expr;
+mod wrapper_collect_visitor;
+
+pub use config::{Config, VanillaExtract, VanillaExtractBuilder};
+pub use debug_id_sanitizer::DebugIdSanitizer;
+pub use debug_id_template::DebugIdTemplate;
+pub use diagnostics::{
+    Applicability, Diagnostic, DiagnosticLog, Fix, Rule, RuleConfig, Severity, TextEdit,
+};
+pub use hmr::HmrRuntime;
+pub use import_filter::ImportFilter;
+pub use package_map::PackageMap;
+pub use report::{StyleModuleReport, StyleModuleReportSlot, StyleReport};
 
-/// Top level visitor for vanilla-extract plugin.
-pub struct VanillaExtractVisitor {
+/// Top level visitor for vanilla-extract plugin. Created by [VanillaExtractBuilder].
+pub struct VanillaExtractVisitor<C: Comments = NoopComments, S: SourceMapper = SourceMap> {
+    source_map: Arc<S>,
+    comments: C,
+    config: Config,
     file_path: String,
-    package_name: String,
+    relative_path: String,
+    file_stem: String,
+    dir: String,
 
     is_css_file: bool,
 }
 
 impl VanillaExtractVisitor {
+    /// Creates a visitor without access to comments or the source map, so marker comments,
+    /// directives and line numbers in debug id templates are not available.
+    #[deprecated(note = "Use `VanillaExtract::builder()` instead")]
     pub fn new(filename: &str, package_name: &str, package_dir: &str) -> Self {
+        VanillaExtract::builder()
+            .package_name(package_name)
+            .package_dir(package_dir)
+            .build_visitor(Arc::new(SourceMap::default()), NoopComments, filename)
+    }
+}
+
+impl<C: Comments, S: SourceMapper> VanillaExtractVisitor<C, S> {
+    fn from_config(source_map: Arc<S>, comments: C, filename: &str, config: Config) -> Self {
+        let file_path = normalize_file_path(&config.package_dir, filename);
+        let relative_path = relative_path(&file_path, &normalize_path(&config.package_dir))
+            .unwrap_or(&file_path)
+            .to_string();
+
         VanillaExtractVisitor {
-            file_path: PathBuf::from(package_dir)
-                .join(filename)
-                .to_slash_lossy()
-                .to_string(),
-            package_name: package_name.to_string(),
+            source_map,
+            comments,
+            config,
+            file_stem: file_stem(&file_path).to_string(),
+            dir: dir_name(&file_path).to_string(),
+            file_path,
+            relative_path,
+
+            is_css_file: CSS_FILE_FILTER_REGEX.is_match(strip_query(filename)),
+        }
+    }
+
+    fn get_package_name(&self) -> &str {
+        self.config
+            .package_map
+            .resolve(&self.relative_path)
+            .unwrap_or(&self.config.package_name)
+    }
+
+    /// Builds the debug id to inject from the candidate found, expanding the template if any and
+    /// sanitizing the result. Its length is capped when it's registered.
+    fn format_debug_id(&self, candidate: DebugIdCandidate, index: usize) -> String {
+        let debug_id = match &self.config.debug_id_template {
+            Some(debug_id_template) if !candidate.is_explicit => {
+                let line = if debug_id_template.uses_line() && !candidate.span.is_dummy() {
+                    self.source_map.lookup_char_pos(candidate.span.lo).line
+                } else {
+                    0
+                };
 
-            is_css_file: CSS_FILE_FILTER_REGEX.is_match(filename),
+                debug_id_template.expand(&DebugIdTemplateContext {
+                    name: &candidate.name,
+                    file_stem: &self.file_stem,
+                    dir: &self.dir,
+                    line,
+                    index,
+                })
+            }
+            _ => candidate.name,
+        };
+
+        match &self.config.debug_id_sanitizer {
+            Some(debug_id_sanitizer) => debug_id_sanitizer.sanitize_chars(&debug_id),
+            None => debug_id,
         }
     }
+
+    /// Returns true if diagnostics of given rule are reported, to skip checks which are off.
+    fn is_enabled(&self, rule: Rule) -> bool {
+        self.config.severity(rule) != Severity::Off
+    }
+
+    /// Reports given diagnostic with the severity configured for its rule.
+    fn report(&self, diagnostic: Diagnostic) {
+        let severity = self.config.severity(diagnostic.rule);
+        emit(
+            Diagnostic {
+                severity,
+                ..diagnostic
+            },
+            self.config.diagnostics.as_ref(),
+        );
+    }
+
+    fn store_report(&self, report: Option<StyleModuleReport>) {
+        if let (Some(slot), Some(report)) = (&self.config.report, report) {
+            slot.set(report);
+        }
+    }
+
+    /// Reports calls to style functions which run while a file which is not a style file is
+    /// evaluated, as vanilla-extract throws when they run outside of a file scope. Calls in
+    /// helpers only run when a style file calls them, within its own file scope.
+    fn check_style_calls_outside_style_file(&self, items: &[ModuleItem]) {
+        if !self.is_enabled(Rule::StyleCallsOutsideStyleFiles) {
+            return;
+        }
+
+        let analysis = ModuleAnalysis::analyze(items);
+        // Compiled files, i.e published vanilla-extract output, set the file scope themselves
+        if analysis.is_compiled
+            || (analysis.namespace_import.is_none() && analysis.import_identifiers.is_empty())
+        {
+            return;
+        }
+
+        for (used_export, span) in find_evaluated_style_calls(items, &analysis) {
+            self.report(Diagnostic::new(
+                Rule::StyleCallsOutsideStyleFiles,
+                span,
+                format!(
+                    "`{}` is called outside of a style file, styles can only be registered in \
+                     .css.ts files",
+                    used_export
+                ),
+            ));
+        }
+    }
+
+    /// Returns true if any of the top level items has the marker comment emitted by this plugin.
+    fn has_file_scope_marker(&self, items: &[ModuleItem]) -> bool {
+        items.iter().any(|item| {
+            self.comments
+                .get_leading(item.span_lo())
+                .is_some_and(|comments| {
+                    comments
+                        .iter()
+                        .any(|comment| comment.text.trim() == FILE_SCOPE_MARKER_COMMENT)
+                })
+        })
+    }
 }
 
-impl VisitMut for VanillaExtractVisitor {
+impl<C: Comments, S: SourceMapper> VisitMut for VanillaExtractVisitor<C, S> {
     fn visit_mut_module_items(
         &mut self,
         items: &mut Vec<ModuleItem>,
         //ast_path: &mut AstNodePath<AstParentNodeRef<'r>>,
     ) {
-        // Bail early if file isn't a .css.ts file
+        // Bail early if file isn't a .css.ts file, after checking it does not register styles
         if !self.is_css_file {
+            self.check_style_calls_outside_style_file(items);
             return;
         }
 
-        let mut new_items = vec![];
-        let mut import_collect_visitor = ImportCollectVisitor::new();
+        // Collects related imports and wrappers first
+        let analysis = ModuleAnalysis::analyze(items);
 
-        // Runs all childrens with import collect visitor to collect related imports first
-        for item in items.iter() {
-            item.visit_children_with(&mut import_collect_visitor);
-        }
+        let mut report = self.config.report.as_ref().map(|_| StyleModuleReport {
+            file_path: self.file_path.clone(),
+            package_name: self.get_package_name().to_string(),
+            ..Default::default()
+        });
 
-        if import_collect_visitor.is_compiled {
+        // Bail early if file has already been compiled
+        if analysis.is_compiled || self.has_file_scope_marker(items) {
+            self.store_report(report);
             return;
         }
 
-        let mut debug_id_find_visitor = DebugIdFindVisitor::new(
-            import_collect_visitor.namespace_import.clone(),
-            import_collect_visitor.import_identifiers.clone(),
-        );
-        let mut debug_id_inject_visitor = DebugIdInjectVisitor::new(
-            import_collect_visitor.namespace_import,
-            import_collect_visitor.import_identifiers,
-        );
+        if self.is_enabled(Rule::LazyStyleCalls) {
+            for lazy_call in find_lazy_style_calls(items, &analysis) {
+                let mut diagnostic = Diagnostic::new(
+                    Rule::LazyStyleCalls,
+                    lazy_call.span,
+                    format!(
+                        "`{}` is called in a function which does not run while the file is \
+                         evaluated, styles can only be registered within the file scope",
+                        lazy_call.used_export
+                    ),
+                );
+                let snippet = self.source_map.span_to_snippet(lazy_call.span).ok();
+                if let (Some(hoist), Some(snippet)) = (lazy_call.hoist, snippet) {
+                    // Inserted before the leading comments of the item, so JSDoc and directives
+                    // stay attached to it
+                    let pos = self
+                        .comments
+                        .get_leading(hoist.pos)
+                        .and_then(|comments| comments.iter().map(|comment| comment.span.lo).min())
+                        .unwrap_or(hoist.pos);
+                    diagnostic = diagnostic.with_fix(Fix::new(
+                        format!("Move the call to the top level as `{}`", hoist.name),
+                        vec![
+                            TextEdit::insert(pos, format!("const {} = {};\n", hoist.name, snippet)),
+                            TextEdit::replace(lazy_call.span, hoist.name),
+                        ],
+                    ));
+                }
+                self.report(diagnostic);
+            }
+        }
+
+        if self.is_enabled(Rule::NonSerializableExports) {
+            for export in find_non_serializable_exports(items) {
+                self.report(Diagnostic::new(
+                    Rule::NonSerializableExports,
+                    export.span,
+                    format!(
+                        "`{}` exports {}, which vanilla-extract can't serialise",
+                        export.export_name, export.kind
+                    ),
+                ));
+            }
+        }
+
+        if self.is_enabled(Rule::UnknownStyleKeys) || self.is_enabled(Rule::UntargetedSelectors) {
+            let issues = validate_style_rules(items, &analysis);
+            for diagnostic in issues.unknown_keys {
+                self.report(diagnostic);
+            }
+            for diagnostic in issues.untargeted_selectors {
+                self.report(diagnostic);
+            }
+        }
+
+        if self.is_enabled(Rule::ThemeContractMismatches) {
+            for (message, span) in find_theme_contract_mismatches(items, &analysis) {
+                self.report(Diagnostic::new(
+                    Rule::ThemeContractMismatches,
+                    span,
+                    message,
+                ));
+            }
+        }
+
+        if self.is_enabled(Rule::UnusedStyles) {
+            for unused_style in find_unused_styles(items, &analysis) {
+                self.report(Diagnostic::new(
+                    Rule::UnusedStyles,
+                    unused_style.span,
+                    format!(
+                        "`{}` is created by `{}` but never used or exported, its CSS is still \
+                         registered and shipped",
+                        unused_style.name, unused_style.used_export
+                    ),
+                ));
+            }
+        }
+
+        if self.is_enabled(Rule::DeniedImports) {
+            for (src, span) in &analysis.imports {
+                if let Some(pattern) = self.config.import_filter.denied_by(src) {
+                    self.report(Diagnostic::new(
+                        Rule::DeniedImports,
+                        *span,
+                        format!("`{}` matches denied import `{}`", src, pattern),
+                    ));
+                }
+            }
+        }
+
+        // File level directives are read from the leading comments of the first item
+        let file_directives = items
+            .first()
+            .map(|item| get_leading_directives(&self.comments, item.span_lo()))
+            .unwrap_or_default();
+        let inject_debug_ids = !file_directives.contains(&Directive::DisableDebugId);
 
-        for mut item in items.drain(..) {
-            // Bail early if file has already been compiled
-            if !debug_id_find_visitor.is_compiled {
-                // First, find debug id with ast_path visitor
+        // Style calls are found along with their debug ids, run it for the report regardless
+        if inject_debug_ids || report.is_some() {
+            let mut reference_collect_visitor = ReferenceCollectVisitor::default();
+            items.visit_with(&mut reference_collect_visitor);
+            let mut debug_id_find_visitor = DebugIdFindVisitor::new(
+                &self.comments,
+                &self.file_stem,
+                &reference_collect_visitor.references,
+                analysis.namespace_import.clone(),
+                analysis.import_identifiers.clone(),
+                analysis.wrappers.clone(),
+            );
+            debug_id_find_visitor.collect_styles = report.is_some();
+            let mut debug_id_inject_visitor = DebugIdInjectVisitor::new(
+                analysis.namespace_import.clone(),
+                analysis.import_identifiers.clone(),
+                analysis.wrappers.clone(),
+            );
+
+            // Find debug ids of all items with ast_path visitor before injecting any, so debug ids
+            // passed explicitly anywhere in the file are not issued to other calls.
+            for item in items.iter() {
                 item.visit_children_with_path(&mut debug_id_find_visitor, &mut Default::default());
+            }
+
+            let mut injected_debug_ids = HashMap::new();
+            if inject_debug_ids {
+                let mut debug_id_index = 0;
+                let mut debug_id_registry =
+                    DebugIdRegistry::new(self.config.debug_id_sanitizer.clone());
+                for (debug_id, span) in debug_id_find_visitor.given_debug_ids.drain(..) {
+                    debug_id_registry.reserve(debug_id, span);
+                }
+
                 // Inject debug id with mutable visitor. This make each node traverses twice, but
                 // mutable visitor does not get the ast_path with node to read its debug id.
-                debug_id_inject_visitor.debug_id = debug_id_find_visitor.debug_id.take();
-                //We'll keep single inject visitor as stateful, visitor will consume debug_id if exists
-                item.visit_mut_children_with(&mut debug_id_inject_visitor);
+                debug_id_inject_visitor.debug_ids = debug_id_find_visitor
+                    .debug_ids
+                    .drain(..)
+                    .map(|candidate| {
+                        candidate.map(|mut candidate| {
+                            let span = candidate.span;
+                            let insertion = candidate.insertion.take();
+                            debug_id_index += 1;
+                            let debug_id = self.format_debug_id(candidate, debug_id_index - 1);
+
+                            let registered = debug_id_registry.register(debug_id.clone(), span);
+                            if let Some(first_span) = registered.collided_with {
+                                let mut diagnostic = Diagnostic::new(
+                                    Rule::DuplicateDebugIds,
+                                    span,
+                                    format!(
+                                        "Debug id `{}` is already used in this file, a numeric \
+                                         suffix is appended",
+                                        debug_id
+                                    ),
+                                )
+                                .with_note(first_span, "First used here");
+                                // Passing the suffixed debug id keeps the output as is
+                                if let Some(insertion) = insertion {
+                                    diagnostic = diagnostic.with_fix(
+                                        Fix::new(
+                                            format!(
+                                                "Pass `{}` as the debug id",
+                                                registered.debug_id
+                                            ),
+                                            vec![insertion.edit(&registered.debug_id)],
+                                        )
+                                        .with_applicability(Applicability::MachineApplicable),
+                                    );
+                                }
+                                self.report(diagnostic);
+                            }
+                            if report.is_some() {
+                                injected_debug_ids.insert(span, registered.debug_id.clone());
+                            }
+                            registered.debug_id
+                        })
+                    })
+                    .collect();
+                //We'll keep single inject visitor as stateful, visitor will consume debug ids in the same order
+                for item in items.iter_mut() {
+                    item.visit_mut_children_with(&mut debug_id_inject_visitor);
+                }
+            }
+
+            if let Some(report) = &mut report {
+                report.styles = debug_id_find_visitor.styles;
+                for style in &mut report.styles {
+                    if let Some(debug_id) = injected_debug_ids.remove(&style.span) {
+                        style.debug_id = Some(debug_id);
+                    }
+                    if style.export_name.is_none() {
+                        style.export_name = style
+                            .local_name
+                            .as_ref()
+                            .and_then(|local_name| analysis.export_name(local_name))
+                            .map(|export_name| export_name.to_string());
+                    }
+                }
             }
-            new_items.push(item);
         }
-        *items = new_items;
 
-        if !debug_id_find_visitor.is_compiled {
-            // Wrap module with file scope calls
+        if let Some(report) = &mut report {
+            report.is_transformed = true;
+        }
+        self.store_report(report);
 
-            // Plugin does not determine type of import to be CJS or ESM - SWC core should transpile
-            // accordingly depends on the config.
-            let import_scope = ModuleItem::ModuleDecl(ModuleDecl::Import(ImportDecl {
+        if file_directives.contains(&Directive::DisableFileScope) {
+            return;
+        }
+
+        // Wrap module with file scope calls
+
+        // Marker comment flags the module as compiled, so running the plugin again is a no-op.
+        let import_scope_span = Span::dummy_with_cmt();
+        self.comments.add_leading(
+            import_scope_span.lo,
+            Comment {
+                kind: CommentKind::Block,
                 span: DUMMY_SP,
-                specifiers: vec![ImportSpecifier::Namespace(ImportStarAsSpecifier {
-                    span: DUMMY_SP,
-                    local: FILE_SCOPE_IMPORT_NAME.clone(),
-                })],
-                src: Box::new(Str::from(FILE_SCOPE_PACKAGE_IDENTIFIER)),
-                type_only: false,
-                asserts: None,
-            }));
-            let set_file_scope = ModuleItem::Stmt(Stmt::Expr(ExprStmt {
+                text: FILE_SCOPE_MARKER_COMMENT.into(),
+            },
+        );
+
+        // Plugin does not determine type of import to be CJS or ESM - SWC core should transpile
+        // accordingly depends on the config.
+        let import_scope = ModuleItem::ModuleDecl(ModuleDecl::Import(ImportDecl {
+            span: import_scope_span,
+            specifiers: vec![ImportSpecifier::Namespace(ImportStarAsSpecifier {
                 span: DUMMY_SP,
-                expr: Box::new(Expr::Call(CallExpr {
+                local: FILE_SCOPE_IMPORT_NAME.clone(),
+            })],
+            src: Box::new(Str::from(FILE_SCOPE_PACKAGE_IDENTIFIER)),
+            type_only: false,
+            asserts: None,
+        }));
+        let set_file_scope = ModuleItem::Stmt(Stmt::Expr(ExprStmt {
+            span: DUMMY_SP,
+            expr: Box::new(Expr::Call(CallExpr {
+                span: DUMMY_SP,
+                callee: Callee::Expr(Box::new(Expr::Member(MemberExpr {
                     span: DUMMY_SP,
-                    callee: Callee::Expr(Box::new(Expr::Member(MemberExpr {
-                        span: DUMMY_SP,
-                        obj: Box::new(Expr::Ident(FILE_SCOPE_IMPORT_NAME.clone())),
-                        prop: MemberProp::Ident(Ident::new("setFileScope".into(), DUMMY_SP)),
-                    }))),
-                    args: vec![
-                        ExprOrSpread {
-                            spread: None,
-                            expr: Box::new(Expr::Lit(Lit::Str(Str::from(JsWord::from(
-                                self.file_path.clone(),
-                            ))))),
-                        },
-                        ExprOrSpread {
-                            spread: None,
-                            expr: Box::new(Expr::Lit(Lit::Str(Str::from(JsWord::from(
-                                self.package_name.clone(),
-                            ))))),
-                        },
-                    ],
-                    type_args: None,
-                })),
-            }));
-
-            items.insert(0, import_scope);
-            items.insert(1, set_file_scope);
-
-            items.push(quote!(
-                "$file_scope_import_name.endFileScope()" as ModuleItem,
-                file_scope_import_name = FILE_SCOPE_IMPORT_NAME.clone()
+                    obj: Box::new(Expr::Ident(FILE_SCOPE_IMPORT_NAME.clone())),
+                    prop: MemberProp::Ident(Ident::new("setFileScope".into(), DUMMY_SP)),
+                }))),
+                args: vec![
+                    ExprOrSpread {
+                        spread: None,
+                        expr: Box::new(Expr::Lit(Lit::Str(Str::from(JsWord::from(
+                            self.file_path.clone(),
+                        ))))),
+                    },
+                    ExprOrSpread {
+                        spread: None,
+                        expr: Box::new(Expr::Lit(Lit::Str(Str::from(JsWord::from(
+                            self.get_package_name(),
+                        ))))),
+                    },
+                ],
+                type_args: None,
+            })),
+        }));
+
+        items.insert(0, import_scope);
+        items.insert(1, set_file_scope);
+
+        items.push(quote!(
+            "$file_scope_import_name.endFileScope()" as ModuleItem,
+            file_scope_import_name = FILE_SCOPE_IMPORT_NAME.clone()
+        ));
+
+        if let Some(hmr) = self.config.hmr {
+            items.push(create_hmr_handler(
+                hmr,
+                self.config.hmr_accept,
+                &self.file_path,
             ));
         }
     }
 }
 
+#[deprecated(note = "Use `VanillaExtract::builder()` instead")]
 pub fn create_extract_visitor<C: Clone + Comments, S: SourceMapper>(
-    _source_map: std::sync::Arc<S>,
-    _comments: C,
+    source_map: std::sync::Arc<S>,
+    comments: C,
     filename: &str,
     package_name: &str,
     package_dir: &str,
-) -> VanillaExtractVisitor {
-    VanillaExtractVisitor::new(filename, package_name, package_dir)
+) -> VanillaExtractVisitor<C, S> {
+    VanillaExtract::builder()
+        .package_name(package_name)
+        .package_dir(package_dir)
+        .build_visitor(source_map, comments, filename)
 }
@@ -0,0 +1,111 @@
+use crate::path_matcher::PathMatcher;
+
+#[derive(Debug, Clone)]
+struct PackageMapEntry {
+    pattern: String,
+    matcher: PathMatcher,
+    package_name: String,
+}
+
+/// Maps source file paths, relative to the package dir, to the package name passed into
+/// `setFileScope`. Allows monorepos to namespace each workspace package without relying on
+/// filesystem discovery of package.json.
+///
+/// When multiple patterns match the same path, the longest (most specific) pattern wins.
+#[derive(Debug, Clone, Default)]
+pub struct PackageMap {
+    entries: Vec<PackageMapEntry>,
+}
+
+impl PackageMap {
+    pub fn new() -> Self {
+        Default::default()
+    }
+
+    /// Adds a path prefix (`packages/button`) or glob (`packages/button/**`) mapped to the given
+    /// package name.
+    pub fn insert(&mut self, pattern: &str, package_name: &str) {
+        self.entries.push(PackageMapEntry {
+            pattern: pattern.to_string(),
+            matcher: PathMatcher::new(pattern),
+            package_name: package_name.to_string(),
+        });
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    /// Returns the package name for the given slash-separated path, if any pattern matches.
+    pub fn resolve(&self, path: &str) -> Option<&str> {
+        let path = path.trim_start_matches("./");
+
+        self.entries
+            .iter()
+            .filter(|entry| entry.matcher.is_match(path))
+            .max_by_key(|entry| entry.pattern.len())
+            .map(|entry| entry.package_name.as_str())
+    }
+}
+
+impl<P: AsRef<str>, N: AsRef<str>> FromIterator<(P, N)> for PackageMap {
+    fn from_iter<T: IntoIterator<Item = (P, N)>>(iter: T) -> Self {
+        let mut map = PackageMap::new();
+        for (pattern, package_name) in iter {
+            map.insert(pattern.as_ref(), package_name.as_ref());
+        }
+        map
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use pretty_assertions::assert_eq;
+
+    use super::*;
+
+    #[test]
+    fn resolves_prefix_and_glob() {
+        let map: PackageMap = [
+            ("packages/button", "@acme/button"),
+            ("apps/*/src/**", "@acme/app"),
+        ]
+        .into_iter()
+        .collect();
+
+        assert_eq!(
+            map.resolve("./packages/button/src/button.css.ts"),
+            Some("@acme/button")
+        );
+        assert_eq!(map.resolve("apps/web/src/theme.css.ts"), Some("@acme/app"));
+        assert_eq!(map.resolve("packages/card/src/card.css.ts"), None);
+    }
+
+    #[test]
+    fn longest_pattern_wins() {
+        let map: PackageMap = [
+            ("packages/**", "@acme/packages"),
+            ("packages/button/**", "@acme/button"),
+            ("packages", "@acme/root"),
+        ]
+        .into_iter()
+        .collect();
+
+        assert_eq!(
+            map.resolve("packages/button/src/button.css.ts"),
+            Some("@acme/button")
+        );
+        assert_eq!(
+            map.resolve("packages/card/src/card.css.ts"),
+            Some("@acme/packages")
+        );
+    }
+
+    #[test]
+    fn resolves_nothing_when_empty() {
+        let map = PackageMap::new();
+
+        assert!(map.is_empty());
+        assert_eq!(map.resolve("src/button.css.ts"), None);
+    }
+}
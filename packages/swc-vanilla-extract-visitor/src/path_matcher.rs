@@ -0,0 +1,106 @@
+use regex::Regex as Regexp;
+
+/// Pattern matching slash-separated paths, i.e package map entries or import specifiers. Patterns
+/// without any wildcard are treated as a path prefix, otherwise it's a glob where `*` / `?` do not
+/// cross a path separator and `**` does.
+#[derive(Debug, Clone)]
+pub(crate) enum PathMatcher {
+    Prefix(String),
+    Glob(Regexp),
+}
+
+impl PathMatcher {
+    pub(crate) fn new(pattern: &str) -> Self {
+        let pattern = pattern.trim_start_matches("./");
+
+        if !pattern.contains(['*', '?']) {
+            return PathMatcher::Prefix(pattern.trim_end_matches('/').to_string());
+        }
+
+        let mut regex = String::from("^");
+        let mut chars = pattern.chars().peekable();
+        while let Some(c) = chars.next() {
+            match c {
+                '*' if chars.peek() == Some(&'*') => {
+                    chars.next();
+                    if chars.peek() == Some(&'/') {
+                        // `**/` matches zero or more directories
+                        chars.next();
+                        regex.push_str("(?:.*/)?");
+                    } else {
+                        regex.push_str(".*");
+                    }
+                }
+                '*' => regex.push_str("[^/]*"),
+                '?' => regex.push_str("[^/]"),
+                c => regex.push_str(&regex::escape(&c.to_string())),
+            }
+        }
+        regex.push('$');
+
+        PathMatcher::Glob(Regexp::new(&regex).expect("Glob pattern should be a valid regex"))
+    }
+
+    pub(crate) fn is_match(&self, path: &str) -> bool {
+        match self {
+            PathMatcher::Prefix(prefix) => {
+                prefix.is_empty()
+                    || path == prefix
+                    || (path.starts_with(prefix.as_str()) && path[prefix.len()..].starts_with('/'))
+            }
+            PathMatcher::Glob(regex) => regex.is_match(path),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn matches_prefix() {
+        let matcher = PathMatcher::new("./packages/button/");
+
+        assert!(matcher.is_match("packages/button"));
+        assert!(matcher.is_match("packages/button/src/button.css.ts"));
+        assert!(!matcher.is_match("packages/button-group/src/button.css.ts"));
+        assert!(!matcher.is_match("packages"));
+    }
+
+    #[test]
+    fn matches_empty_prefix() {
+        assert!(PathMatcher::new("").is_match("src/button.css.ts"));
+    }
+
+    #[test]
+    fn matches_single_star_within_segment() {
+        let matcher = PathMatcher::new("packages/*/src");
+
+        assert!(matcher.is_match("packages/button/src"));
+        assert!(!matcher.is_match("packages/button/nested/src"));
+        assert!(!matcher.is_match("packages/button/src/button.css.ts"));
+    }
+
+    #[test]
+    fn matches_double_star_across_segments() {
+        let matcher = PathMatcher::new("packages/**/*.css.ts");
+
+        assert!(matcher.is_match("packages/button.css.ts"));
+        assert!(matcher.is_match("packages/button/src/button.css.ts"));
+        assert!(!matcher.is_match("packages/button/src/button.ts"));
+
+        let matcher = PathMatcher::new("packages/button/**");
+
+        assert!(matcher.is_match("packages/button/src/button.css.ts"));
+        assert!(!matcher.is_match("packages/card/src/card.css.ts"));
+    }
+
+    #[test]
+    fn matches_question_mark_and_escapes_literals() {
+        let matcher = PathMatcher::new("src/v?/theme.css.ts");
+
+        assert!(matcher.is_match("src/v2/theme.css.ts"));
+        assert!(!matcher.is_match("src/v/theme.css.ts"));
+        assert!(!matcher.is_match("src/v2/themeXcss.ts"));
+    }
+}
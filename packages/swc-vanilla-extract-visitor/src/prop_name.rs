@@ -0,0 +1,12 @@
+use swc_core::ecma::ast::PropName;
+
+/// Returns the name of a static property key, `None` for computed keys. String and numeric keys
+/// are returned verbatim.
+pub(crate) fn get_prop_name(prop_name: &PropName) -> Option<String> {
+    match prop_name {
+        PropName::Ident(ident) => Some(ident.sym.to_string()),
+        PropName::Str(str) => Some(str.value.to_string()),
+        PropName::Num(num) => Some(num.value.to_string()),
+        _ => None,
+    }
+}
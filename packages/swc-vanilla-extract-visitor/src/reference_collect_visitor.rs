@@ -0,0 +1,67 @@
+use std::collections::HashMap;
+
+use swc_core::ecma::{
+    ast::{ExportNamedSpecifier, Expr, ModuleExportName, NamedExport, Prop},
+    atoms::JsWord,
+    visit::{Visit, VisitWith},
+};
+
+/// Collects the identifiers read by the visited nodes, regardless of the scope they resolve in,
+/// and whether the nodes depend on the function they are in through `this`, `arguments`, `super`,
+/// `new.target`, `await` or `yield`.
+#[derive(Default)]
+pub(crate) struct ReferenceCollectVisitor {
+    /// Number of reads of each identifier.
+    pub references: HashMap<JsWord, usize>,
+    pub is_contextual: bool,
+}
+
+impl ReferenceCollectVisitor {
+    pub fn is_referenced(&self, sym: &JsWord) -> bool {
+        self.references.contains_key(sym)
+    }
+
+    fn add(&mut self, sym: &JsWord) {
+        *self.references.entry(sym.clone()).or_default() += 1;
+    }
+}
+
+impl Visit for ReferenceCollectVisitor {
+    fn visit_expr(&mut self, expr: &Expr) {
+        match expr {
+            Expr::Ident(ident) if &*ident.sym == "arguments" => self.is_contextual = true,
+            Expr::Ident(ident) => self.add(&ident.sym),
+            Expr::This(..)
+            | Expr::SuperProp(..)
+            | Expr::MetaProp(..)
+            | Expr::Await(..)
+            | Expr::Yield(..) => self.is_contextual = true,
+            _ => {}
+        }
+
+        expr.visit_children_with(self);
+    }
+
+    fn visit_named_export(&mut self, named_export: &NamedExport) {
+        // Re-exports from other modules do not read bindings of this file
+        if named_export.src.is_none() {
+            named_export.visit_children_with(self);
+        }
+    }
+
+    fn visit_export_named_specifier(&mut self, specifier: &ExportNamedSpecifier) {
+        // `export { root }`
+        if let ModuleExportName::Ident(orig) = &specifier.orig {
+            self.add(&orig.sym);
+        }
+    }
+
+    fn visit_prop(&mut self, prop: &Prop) {
+        // `{ color }`
+        if let Prop::Shorthand(ident) = prop {
+            self.add(&ident.sym);
+        }
+
+        prop.visit_children_with(self);
+    }
+}
@@ -0,0 +1,56 @@
+use std::sync::{Arc, Mutex};
+
+use swc_core::common::Span;
+
+/// A call to a vanilla-extract style function, or to a local wrapper of one.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct StyleReport {
+    /// Name of the vanilla-extract function called, i.e `style`, `recipe`, `createTheme`. For calls
+    /// to local wrappers, name of the wrapper function.
+    pub kind: String,
+    /// True if the call is a call to a local wrapper function forwarding the debug id.
+    pub is_wrapper: bool,
+    /// Top level binding containing the call, i.e `button` for `const button = { root: style() }`.
+    pub local_name: Option<String>,
+    /// Name the containing top level binding is exported as, `default` for default exports.
+    pub export_name: Option<String>,
+    /// Debug id injected into the call, or given by the call already.
+    pub debug_id: Option<String>,
+    pub span: Span,
+}
+
+/// Analysis of a style file transformed by the visitor.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct StyleModuleReport {
+    /// Normalised absolute path of the file.
+    pub file_path: String,
+    /// Package name used for the file scope.
+    pub package_name: String,
+    /// False if the file has been skipped, i.e because it has already been compiled.
+    pub is_transformed: bool,
+    /// Style calls in traversal order.
+    pub styles: Vec<StyleReport>,
+}
+
+/// Slot shared with the visitor to read the report of the transformed file after the pass. The
+/// slot is left empty for files which are not style files.
+#[derive(Debug, Clone, Default)]
+pub struct StyleModuleReportSlot(Arc<Mutex<Option<StyleModuleReport>>>);
+
+impl StyleModuleReportSlot {
+    pub fn new() -> Self {
+        Default::default()
+    }
+
+    /// Takes the report out of the slot, leaving it empty for the next file.
+    pub fn take(&self) -> Option<StyleModuleReport> {
+        self.0
+            .lock()
+            .expect("Report slot should not be poisoned")
+            .take()
+    }
+
+    pub(crate) fn set(&self, report: StyleModuleReport) {
+        *self.0.lock().expect("Report slot should not be poisoned") = Some(report);
+    }
+}
@@ -0,0 +1,107 @@
+use std::ops::Range;
+
+/// Splits given selector list on its top level commas, returning the byte range of each selector
+/// without surrounding whitespace.
+fn split_selector_list(selector_list: &str) -> Vec<Range<usize>> {
+    let mut selectors = vec![];
+    let mut depth = 0usize;
+    let mut quote = None;
+    let mut start = 0;
+
+    let mut push = |range: Range<usize>| {
+        let selector = &selector_list[range.clone()];
+        let trimmed_start = selector.len() - selector.trim_start().len();
+        let trimmed_end = selector.trim_end().len();
+        if trimmed_start < trimmed_end {
+            selectors.push(range.start + trimmed_start..range.start + trimmed_end);
+        }
+    };
+
+    let mut chars = selector_list.char_indices();
+    while let Some((index, char)) = chars.next() {
+        match (char, quote) {
+            ('\\', _) => {
+                chars.next();
+            }
+            (_, Some(quote_char)) if char == quote_char => quote = None,
+            (_, Some(..)) => {}
+            ('"' | '\'', None) => quote = Some(char),
+            ('(' | '[', None) => depth += 1,
+            (')' | ']', None) => depth = depth.saturating_sub(1),
+            (',', None) if depth == 0 => {
+                push(start..index);
+                start = index + 1;
+            }
+            _ => {}
+        }
+    }
+    push(start..selector_list.len());
+
+    selectors
+}
+
+/// Returns true if the subject of given complex selector, its last compound selector, contains
+/// `&`, i.e `.parent &:hover` but not `& .child`.
+fn targets_parent(selector: &str) -> bool {
+    let mut depth = 0usize;
+    let mut quote = None;
+    let mut subject_start = 0;
+
+    let mut chars = selector.char_indices();
+    while let Some((index, char)) = chars.next() {
+        match (char, quote) {
+            ('\\', _) => {
+                chars.next();
+            }
+            (_, Some(quote_char)) if char == quote_char => quote = None,
+            (_, Some(..)) => {}
+            ('"' | '\'', None) => quote = Some(char),
+            ('(' | '[', None) => depth += 1,
+            (')' | ']', None) => depth = depth.saturating_sub(1),
+            (' ' | '\t' | '\n' | '>' | '+' | '~', None) if depth == 0 => {
+                subject_start = index + 1;
+            }
+            _ => {}
+        }
+    }
+
+    selector[subject_start..].contains('&')
+}
+
+/// Returns the byte ranges of the selectors in given selector list which do not target `&`, as
+/// vanilla-extract requires selectors of a style to target the element it's applied to.
+pub fn find_untargeted_selectors(selector_list: &str) -> Vec<Range<usize>> {
+    split_selector_list(selector_list)
+        .into_iter()
+        .filter(|range| !targets_parent(&selector_list[range.clone()]))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use pretty_assertions::assert_eq;
+
+    use super::*;
+
+    fn find(selector_list: &str) -> Vec<&str> {
+        find_untargeted_selectors(selector_list)
+            .into_iter()
+            .map(|range| &selector_list[range])
+            .collect()
+    }
+
+    #[test]
+    fn allows_selectors_targeting_parent() {
+        assert_eq!(find("&:hover"), Vec::<&str>::new());
+        assert_eq!(find(".parent &, &:focus-visible"), Vec::<&str>::new());
+        assert_eq!(find("${parent} > &[data-state='a b']"), Vec::<&str>::new());
+        assert_eq!(find("&:not(.a .b)"), Vec::<&str>::new());
+    }
+
+    #[test]
+    fn finds_selectors_not_targeting_parent() {
+        assert_eq!(find(".parent .child"), vec![".parent .child"]);
+        assert_eq!(find("&:hover, & > span"), vec!["& > span"]);
+        assert_eq!(find(":is(&) ${child}"), vec![":is(&) ${child}"]);
+    }
+}
@@ -0,0 +1,212 @@
+use std::collections::HashMap;
+
+use swc_core::{
+    common::{Span, Spanned},
+    ecma::{
+        ast::{
+            Decl, DefaultDecl, ExportSpecifier, Expr, ModuleDecl, ModuleExportName, ModuleItem,
+            Pat, Prop, PropOrSpread, Stmt,
+        },
+        atoms::JsWord,
+    },
+};
+
+use crate::unwrap_expr::unwrap_expr;
+
+/// An export of a style file vanilla-extract can't serialise.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct NonSerializableExport {
+    /// Name the value is exported as, `default` for default exports.
+    pub export_name: String,
+    /// What the value is, i.e `a function`.
+    pub kind: &'static str,
+    /// Span of the value, or of the property of the value which can't be serialised.
+    pub span: Span,
+}
+
+/// Top level binding an export can refer to.
+enum Binding<'a> {
+    Expr(&'a Expr),
+    Function(Span),
+    Class(Span),
+}
+
+/// Returns what given expression is if it's statically known to be non-serialisable, along with
+/// the span of the offending value. Object and array literals are checked recursively, calls are
+/// assumed to return serialisable values, as functions created by `recipe` or `createSprinkles`
+/// are.
+fn get_non_serializable_kind(expr: &Expr) -> Option<(&'static str, Span)> {
+    let expr = unwrap_expr(expr);
+    match expr {
+        Expr::Arrow(..) | Expr::Fn(..) => Some(("a function", expr.span())),
+        Expr::Class(..) => Some(("a class", expr.span())),
+        Expr::New(..) => Some(("a class instance", expr.span())),
+        Expr::Array(array_lit) => array_lit
+            .elems
+            .iter()
+            .flatten()
+            .find_map(|elem| get_non_serializable_kind(&elem.expr)),
+        Expr::Object(object_lit) => object_lit.props.iter().find_map(|prop| match prop {
+            PropOrSpread::Prop(prop) => match &**prop {
+                Prop::KeyValue(key_value) => get_non_serializable_kind(&key_value.value),
+                Prop::Method(method) => Some(("a function", method.function.span)),
+                _ => None,
+            },
+            PropOrSpread::Spread(spread) => get_non_serializable_kind(&spread.expr),
+        }),
+        _ => None,
+    }
+}
+
+fn get_binding_kind(binding: &Binding<'_>) -> Option<(&'static str, Span)> {
+    match binding {
+        Binding::Expr(expr) => get_non_serializable_kind(expr),
+        Binding::Function(span) => Some(("a function", *span)),
+        Binding::Class(span) => Some(("a class", *span)),
+    }
+}
+
+/// Returns the bindings declared by given declaration, with their name.
+fn get_decl_bindings(decl: &Decl) -> Vec<(&JsWord, Binding<'_>)> {
+    match decl {
+        Decl::Var(var_decl) => var_decl
+            .decls
+            .iter()
+            .filter_map(|declarator| match (&declarator.name, &declarator.init) {
+                (Pat::Ident(ident), Some(init)) => Some((&ident.id.sym, Binding::Expr(init))),
+                _ => None,
+            })
+            .collect(),
+        Decl::Fn(fn_decl) => vec![(&fn_decl.ident.sym, Binding::Function(fn_decl.span()))],
+        Decl::Class(class_decl) => vec![(&class_decl.ident.sym, Binding::Class(class_decl.span()))],
+        _ => vec![],
+    }
+}
+
+/// Finds the exports of a style file which are statically known to be non-serialisable, i.e
+/// functions, classes and class instances.
+pub fn find_non_serializable_exports(items: &[ModuleItem]) -> Vec<NonSerializableExport> {
+    // Bindings declared at top level, for exports referring to them by name
+    let bindings: HashMap<&JsWord, Binding<'_>> = items
+        .iter()
+        .flat_map(|item| match item {
+            ModuleItem::Stmt(Stmt::Decl(decl)) => get_decl_bindings(decl),
+            _ => vec![],
+        })
+        .collect();
+
+    let mut exports = vec![];
+    let mut check = |export_name: &str, kind: Option<(&'static str, Span)>| {
+        if let Some((kind, span)) = kind {
+            exports.push(NonSerializableExport {
+                export_name: export_name.to_string(),
+                kind,
+                span,
+            });
+        }
+    };
+
+    for item in items {
+        let module_decl = match item {
+            ModuleItem::ModuleDecl(module_decl) => module_decl,
+            _ => continue,
+        };
+
+        match module_decl {
+            ModuleDecl::ExportDecl(export_decl) => {
+                for (name, binding) in get_decl_bindings(&export_decl.decl) {
+                    check(name, get_binding_kind(&binding));
+                }
+            }
+            ModuleDecl::ExportDefaultDecl(export_default_decl) => match &export_default_decl.decl {
+                DefaultDecl::Fn(fn_expr) => check("default", Some(("a function", fn_expr.span()))),
+                DefaultDecl::Class(class_expr) => {
+                    check("default", Some(("a class", class_expr.span())))
+                }
+                _ => {}
+            },
+            ModuleDecl::ExportDefaultExpr(export_default_expr) => {
+                let kind = match &*export_default_expr.expr {
+                    Expr::Ident(ident) => bindings.get(&ident.sym).and_then(get_binding_kind),
+                    expr => get_non_serializable_kind(expr),
+                };
+                check("default", kind);
+            }
+            // `export { button as default }`, re-exports of other modules are not checked
+            ModuleDecl::ExportNamed(named_export) if named_export.src.is_none() => {
+                for specifier in &named_export.specifiers {
+                    if let ExportSpecifier::Named(named_specifier) = specifier {
+                        let (local, exported) =
+                            match (&named_specifier.orig, &named_specifier.exported) {
+                                (
+                                    ModuleExportName::Ident(local),
+                                    Some(ModuleExportName::Ident(exported)),
+                                ) => (&local.sym, &*exported.sym),
+                                (
+                                    ModuleExportName::Ident(local),
+                                    Some(ModuleExportName::Str(exported)),
+                                ) => (&local.sym, &*exported.value),
+                                (ModuleExportName::Ident(local), None) => (&local.sym, &*local.sym),
+                                _ => continue,
+                            };
+                        check(exported, bindings.get(local).and_then(get_binding_kind));
+                    }
+                }
+            }
+            _ => {}
+        }
+    }
+
+    exports
+}
+
+#[cfg(test)]
+mod tests {
+    use pretty_assertions::assert_eq;
+    use swc_core::quote;
+
+    use super::*;
+
+    fn find(items: Vec<ModuleItem>) -> Vec<(String, &'static str)> {
+        find_non_serializable_exports(&items)
+            .into_iter()
+            .map(|export| (export.export_name, export.kind))
+            .collect()
+    }
+
+    #[test]
+    fn allows_serializable_exports() {
+        let items = vec![
+            quote!("export const root = style({});" as ModuleItem),
+            quote!("export const button = recipe({});" as ModuleItem),
+            quote!("export const sizes = { small: 1, large: [2, 'large'] };" as ModuleItem),
+            quote!("const handler = () => {};" as ModuleItem),
+            quote!("export default root;" as ModuleItem),
+        ];
+
+        assert_eq!(find(items), vec![]);
+    }
+
+    #[test]
+    fn finds_non_serializable_exports() {
+        let items = vec![
+            quote!("export const createStyle = () => style({});" as ModuleItem),
+            quote!("export const theme = { colors: { onClick() {} } };" as ModuleItem),
+            quote!("export const store = new Map();" as ModuleItem),
+            quote!("const Button = class {};" as ModuleItem),
+            quote!("export { Button as Component };" as ModuleItem),
+            quote!("export default (function () {});" as ModuleItem),
+        ];
+
+        assert_eq!(
+            find(items),
+            vec![
+                ("createStyle".to_string(), "a function"),
+                ("theme".to_string(), "a function"),
+                ("store".to_string(), "a class instance"),
+                ("Component".to_string(), "a class"),
+                ("default".to_string(), "a function"),
+            ]
+        );
+    }
+}
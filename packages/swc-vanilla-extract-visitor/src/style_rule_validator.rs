@@ -0,0 +1,608 @@
+use std::{collections::HashSet, ops::Range};
+
+use once_cell::sync::Lazy;
+use swc_core::{
+    common::{BytePos, Span, Spanned},
+    ecma::{
+        ast::{CallExpr, Expr, Lit, ModuleItem, Prop, PropName, PropOrSpread, Str, Tpl},
+        visit::{Visit, VisitWith},
+    },
+};
+
+use crate::{
+    analysis::ModuleAnalysis,
+    css_properties::CSS_PROPERTIES,
+    diagnostics::{Diagnostic, Fix, Rule, TextEdit},
+    get_relavant_call::get_relavant_call,
+    prop_name::get_prop_name,
+    selector::find_untargeted_selectors,
+    unwrap_expr::unwrap_expr,
+};
+
+/// At-rules vanilla-extract accepts in style rules, each mapping queries to nested rules.
+static AT_RULE_KEYS: [&str; 4] = ["@media", "@supports", "@container", "@layer"];
+
+/// Vendor prefixes of property keys, vendor specific properties are not checked.
+static VENDOR_PREFIXES: [&str; 4] = ["Webkit", "Moz", "ms", "O"];
+
+static KNOWN_PROPERTIES: Lazy<HashSet<&str>> = Lazy::new(|| CSS_PROPERTIES.into_iter().collect());
+
+/// Known properties in camel case, to suggest the closest one for an unknown key.
+static CAMEL_CASE_PROPERTIES: Lazy<Vec<String>> = Lazy::new(|| {
+    CSS_PROPERTIES
+        .iter()
+        .map(|property| {
+            let mut parts = property.split('-');
+            let mut camel_case = parts.next().unwrap_or_default().to_string();
+            for part in parts {
+                let mut chars = part.chars();
+                if let Some(first) = chars.next() {
+                    camel_case.extend(first.to_uppercase());
+                    camel_case.push_str(chars.as_str());
+                }
+            }
+            camel_case
+        })
+        .collect()
+});
+
+/// Returns the kebab case name of given property key, i.e `background-color` for
+/// `backgroundColor`. Keys already in kebab case are returned as is.
+fn to_kebab_case(key: &str) -> String {
+    let mut kebab_case = String::with_capacity(key.len() + 4);
+    for char in key.chars() {
+        if char.is_ascii_uppercase() {
+            kebab_case.push('-');
+            kebab_case.push(char.to_ascii_lowercase());
+        } else {
+            kebab_case.push(char);
+        }
+    }
+    kebab_case
+}
+
+fn has_vendor_prefix(key: &str) -> bool {
+    key.starts_with('-')
+        || VENDOR_PREFIXES.iter().any(|prefix| {
+            key.strip_prefix(prefix)
+                .and_then(|rest| rest.chars().next())
+                .is_some_and(|char| char.is_ascii_uppercase())
+        })
+}
+
+fn levenshtein_distance(a: &str, b: &str) -> usize {
+    let b: Vec<char> = b.chars().collect();
+    let mut row: Vec<usize> = (0..=b.len()).collect();
+    for (i, a_char) in a.chars().enumerate() {
+        let mut previous = row[0];
+        row[0] = i + 1;
+        for (j, b_char) in b.iter().enumerate() {
+            let substitution = previous + usize::from(a_char != *b_char);
+            previous = row[j + 1];
+            row[j + 1] = substitution.min(previous + 1).min(row[j] + 1);
+        }
+    }
+    row[b.len()]
+}
+
+/// Returns the known property closest to given key, if it's close enough to be a typo. Short keys
+/// only allow a single edit, as most short words are close to some property.
+fn suggest_property(key: &str) -> Option<&'static str> {
+    let max_distance = (key.len() / 3).min(2);
+    CAMEL_CASE_PROPERTIES
+        .iter()
+        .map(|property| (levenshtein_distance(key, property), property))
+        .filter(|(distance, _)| *distance <= max_distance)
+        .min_by_key(|(distance, _)| *distance)
+        .map(|(_, property)| property.as_str())
+}
+
+/// Returns the value of given key in an object literal.
+fn get_object_value<'a>(expr: &'a Expr, key_name: &str) -> Option<&'a Expr> {
+    let object_lit = match unwrap_expr(expr) {
+        Expr::Object(object_lit) => object_lit,
+        _ => return None,
+    };
+
+    object_lit.props.iter().find_map(|prop| match prop {
+        PropOrSpread::Prop(prop) => match &**prop {
+            Prop::KeyValue(key_value) => get_prop_name(&key_value.key)
+                .filter(|name| name == key_name)
+                .map(|_| &*key_value.value),
+            _ => None,
+        },
+        PropOrSpread::Spread(..) => None,
+    })
+}
+
+/// Returns the values of an object literal, i.e the variants of a `styleVariants` call.
+fn get_object_values(expr: &Expr) -> Vec<&Expr> {
+    match unwrap_expr(expr) {
+        Expr::Object(object_lit) => object_lit
+            .props
+            .iter()
+            .filter_map(|prop| match prop {
+                PropOrSpread::Prop(prop) => match &**prop {
+                    Prop::KeyValue(key_value) => Some(&*key_value.value),
+                    _ => None,
+                },
+                PropOrSpread::Spread(..) => None,
+            })
+            .collect(),
+        _ => vec![],
+    }
+}
+
+/// Static key of a `selectors` entry, along with the source position of each of its bytes when
+/// it's written without escapes, to report a selector of the list precisely.
+struct SelectorKey {
+    text: String,
+    positions: Option<Vec<BytePos>>,
+    span: Span,
+}
+
+impl SelectorKey {
+    /// Reads a string key, or a computed template literal key whose interpolations are assumed to
+    /// be class names, i.e `` [`${parent} &`] ``. Interpolations are kept as written in the text.
+    fn from_prop_name(key: &PropName) -> Option<Self> {
+        match key {
+            PropName::Str(str) => Some(Self::from_str(str)),
+            PropName::Computed(computed) => match unwrap_expr(&computed.expr) {
+                Expr::Lit(Lit::Str(str)) => Some(Self::from_str(str)),
+                Expr::Tpl(tpl) => Self::from_tpl(tpl),
+                _ => None,
+            },
+            _ => None,
+        }
+    }
+
+    fn from_str(str: &Str) -> Self {
+        let is_verbatim = str
+            .raw
+            .as_ref()
+            .is_some_and(|raw| raw.len() >= 2 && raw[1..raw.len() - 1] == *str.value);
+
+        SelectorKey {
+            text: str.value.to_string(),
+            positions: is_verbatim.then(|| {
+                (0..str.value.len())
+                    .map(|index| str.span.lo + BytePos(1 + index as u32))
+                    .collect()
+            }),
+            span: str.span,
+        }
+    }
+
+    fn from_tpl(tpl: &Tpl) -> Option<Self> {
+        let mut text = String::new();
+        let mut positions = vec![];
+        // Nodes created by transforms have no position
+        let mut is_verbatim = !tpl.span.is_dummy();
+
+        for (index, quasi) in tpl.quasis.iter().enumerate() {
+            let cooked = quasi.cooked.as_ref()?;
+            text.push_str(cooked);
+            is_verbatim &= *cooked == quasi.raw;
+            positions.extend((0..cooked.len()).map(|index| quasi.span.lo + BytePos(index as u32)));
+
+            if let (Some(expr), Some(next_quasi)) =
+                (tpl.exprs.get(index), tpl.quasis.get(index + 1))
+            {
+                let placeholder = match &**expr {
+                    Expr::Ident(ident) => format!("${{{}}}", ident.sym),
+                    _ => "${...}".to_string(),
+                };
+                // `${` starts where the quasi ends, `}` ends where the next quasi starts
+                positions.extend((1..placeholder.len()).map(|_| quasi.span.hi));
+                positions.push(BytePos(next_quasi.span.lo.0.saturating_sub(1)));
+                text.push_str(&placeholder);
+            }
+        }
+
+        Some(SelectorKey {
+            text,
+            positions: is_verbatim.then_some(positions),
+            span: tpl.span,
+        })
+    }
+
+    /// Returns the span of given byte range of the key, or the span of the whole key if it's
+    /// written with escapes.
+    fn span_of(&self, range: Range<usize>) -> Span {
+        match &self.positions {
+            Some(positions) if range.start < range.end => self
+                .span
+                .with_lo(positions[range.start])
+                .with_hi(positions[range.end - 1] + BytePos(1)),
+            _ => self.span,
+        }
+    }
+}
+
+/// Checks the keys of object literals given to style functions, i.e `style`, `globalStyle`,
+/// `styleVariants` and `recipe`, against known CSS properties and the keys vanilla-extract nests
+/// rules under.
+struct StyleRuleValidator<'a> {
+    analysis: &'a ModuleAnalysis,
+
+    issues: StyleRuleIssues,
+}
+
+impl<'a> StyleRuleValidator<'a> {
+    /// Checks a style rule, or an array of rules and class names composed by `style`.
+    fn check_rule(&mut self, expr: &Expr) {
+        let object_lit = match unwrap_expr(expr) {
+            Expr::Object(object_lit) => object_lit,
+            Expr::Array(array_lit) => {
+                for elem in array_lit.elems.iter().flatten() {
+                    self.check_rule(&elem.expr);
+                }
+                return;
+            }
+            _ => return,
+        };
+
+        for prop in &object_lit.props {
+            let prop = match prop {
+                PropOrSpread::Prop(prop) => prop,
+                PropOrSpread::Spread(..) => continue,
+            };
+
+            match &**prop {
+                Prop::KeyValue(key_value) => {
+                    // Computed keys are not checked
+                    if let Some(name) = get_prop_name(&key_value.key) {
+                        self.check_key(&name, key_value.key.span(), &key_value.value);
+                    }
+                }
+                // `{ color }`
+                Prop::Shorthand(ident) => self.check_property(&ident.sym, ident.span, true),
+                _ => {}
+            }
+        }
+    }
+
+    /// Checks the rules nested under a map, i.e the selectors of `selectors` or the queries of
+    /// `@media`.
+    fn check_nested_rules(&mut self, expr: &Expr) {
+        for value in get_object_values(expr) {
+            self.check_rule(value);
+        }
+    }
+
+    /// Checks the selectors of a `selectors` map target `&`, and the rules nested under them.
+    fn check_selectors(&mut self, expr: &Expr) {
+        let object_lit = match unwrap_expr(expr) {
+            Expr::Object(object_lit) => object_lit,
+            _ => return,
+        };
+
+        for prop in &object_lit.props {
+            let key_value = match prop {
+                PropOrSpread::Prop(prop) => match &**prop {
+                    Prop::KeyValue(key_value) => key_value,
+                    _ => continue,
+                },
+                PropOrSpread::Spread(..) => continue,
+            };
+
+            if let Some(key) = SelectorKey::from_prop_name(&key_value.key) {
+                for range in find_untargeted_selectors(&key.text) {
+                    let message = format!(
+                        "Selector `{}` does not target `&`, selectors of a style can only target \
+                         the element it's applied to",
+                        &key.text[range.clone()]
+                    );
+                    self.issues.untargeted_selectors.push(Diagnostic::new(
+                        Rule::UntargetedSelectors,
+                        key.span_of(range),
+                        message,
+                    ));
+                }
+            }
+            self.check_rule(&key_value.value);
+        }
+    }
+
+    fn check_key(&mut self, name: &str, span: Span, value: &Expr) {
+        match name {
+            "selectors" => self.check_selectors(value),
+            // Values of CSS variables are not properties
+            "vars" => {}
+            name if AT_RULE_KEYS.contains(&name) => self.check_nested_rules(value),
+            // Pseudo selectors, i.e `:hover` or `::before`
+            name if name.starts_with(':') => self.check_rule(value),
+            name if name.starts_with('@') => self.check_at_rule(name, span),
+            name => self.check_property(name, span, false),
+        }
+    }
+
+    fn check_at_rule(&mut self, name: &str, span: Span) {
+        let at_rule = name.split_whitespace().next().unwrap_or(name);
+        let message = if AT_RULE_KEYS.contains(&at_rule) {
+            format!(
+                "`{}` must be nested under `'{}'`, i.e `'{}': {{ '{}': {{ ... }} }}`",
+                name,
+                at_rule,
+                at_rule,
+                name[at_rule.len()..].trim()
+            )
+        } else {
+            format!(
+                "Unknown at-rule `{}`, style rules only support `@media`, `@supports`, \
+                 `@container` and `@layer`",
+                name
+            )
+        };
+        self.issues
+            .unknown_keys
+            .push(Diagnostic::new(Rule::UnknownStyleKeys, span, message));
+    }
+
+    /// Checks given property key, `is_shorthand` if it's the key of a shorthand property, which
+    /// keeps its value when replaced with a suggestion.
+    fn check_property(&mut self, name: &str, span: Span, is_shorthand: bool) {
+        // Custom properties and vendor specific properties
+        if name.starts_with("--") || has_vendor_prefix(name) {
+            return;
+        }
+        if KNOWN_PROPERTIES.contains(&*to_kebab_case(name)) {
+            return;
+        }
+
+        let diagnostic = match suggest_property(name) {
+            // Suggestions are camel case identifiers, which replace string keys as well
+            Some(suggestion) => {
+                let replacement = if is_shorthand {
+                    format!("{}: {}", suggestion, name)
+                } else {
+                    suggestion.to_string()
+                };
+                Diagnostic::new(
+                    Rule::UnknownStyleKeys,
+                    span,
+                    format!(
+                        "Unknown CSS property `{}`, did you mean `{}`?",
+                        name, suggestion
+                    ),
+                )
+                .with_fix(Fix::new(
+                    format!("Replace with `{}`", suggestion),
+                    vec![TextEdit::replace(span, replacement)],
+                ))
+            }
+            None => Diagnostic::new(
+                Rule::UnknownStyleKeys,
+                span,
+                format!("Unknown CSS property `{}`", name),
+            ),
+        };
+        self.issues.unknown_keys.push(diagnostic);
+    }
+}
+
+impl<'a> Visit for StyleRuleValidator<'a> {
+    fn visit_call_expr(&mut self, call_expr: &CallExpr) {
+        let used_export = get_relavant_call(
+            call_expr,
+            &self.analysis.namespace_import,
+            &self.analysis.import_identifiers,
+        );
+        let arg = |index: usize| {
+            call_expr
+                .args
+                .get(index)
+                .filter(|arg| arg.spread.is_none())
+                .map(|arg| &*arg.expr)
+        };
+
+        match used_export.as_deref() {
+            Some("style") => {
+                if let Some(rule) = arg(0) {
+                    self.check_rule(rule);
+                }
+            }
+            Some("globalStyle") => {
+                if let Some(rule) = arg(1) {
+                    self.check_rule(rule);
+                }
+            }
+            Some("styleVariants") => {
+                // Values given with a mapper function are not rules
+                let has_mapper = arg(1).is_some_and(|arg| !matches!(arg, Expr::Lit(Lit::Str(..))));
+                if let (Some(variants), false) = (arg(0), has_mapper) {
+                    self.check_nested_rules(variants);
+                }
+            }
+            Some("recipe") => {
+                if let Some(options) = arg(0) {
+                    if let Some(base) = get_object_value(options, "base") {
+                        self.check_rule(base);
+                    }
+                    if let Some(variants) = get_object_value(options, "variants") {
+                        for variant in get_object_values(variants) {
+                            self.check_nested_rules(variant);
+                        }
+                    }
+                    if let Some(Expr::Array(compound_variants)) =
+                        get_object_value(options, "compoundVariants").map(unwrap_expr)
+                    {
+                        for compound_variant in compound_variants.elems.iter().flatten() {
+                            if let Some(rule) = get_object_value(&compound_variant.expr, "style") {
+                                self.check_rule(rule);
+                            }
+                        }
+                    }
+                }
+            }
+            _ => {}
+        }
+
+        call_expr.visit_children_with(self);
+    }
+}
+
+/// Issues found in literal style rules.
+#[derive(Debug, Default)]
+pub struct StyleRuleIssues {
+    /// Keys which are neither known CSS properties nor keys vanilla-extract nests rules under.
+    pub unknown_keys: Vec<Diagnostic>,
+    /// Selectors of `selectors` maps which do not target `&`, which vanilla-extract throws for.
+    pub untargeted_selectors: Vec<Diagnostic>,
+}
+
+/// Checks the literal style rules given to style functions.
+pub fn validate_style_rules(items: &[ModuleItem], analysis: &ModuleAnalysis) -> StyleRuleIssues {
+    let mut validator = StyleRuleValidator {
+        analysis,
+        issues: Default::default(),
+    };
+    for item in items {
+        item.visit_with(&mut validator);
+    }
+
+    validator.issues
+}
+
+#[cfg(test)]
+mod tests {
+    use pretty_assertions::assert_eq;
+    use swc_core::quote;
+
+    use super::*;
+
+    fn find(items: Vec<ModuleItem>) -> Vec<String> {
+        let analysis = ModuleAnalysis::analyze(&items);
+        validate_style_rules(&items, &analysis)
+            .unknown_keys
+            .into_iter()
+            .map(|diagnostic| diagnostic.message)
+            .collect()
+    }
+
+    fn find_selectors(items: Vec<ModuleItem>) -> Vec<String> {
+        let analysis = ModuleAnalysis::analyze(&items);
+        validate_style_rules(&items, &analysis)
+            .untargeted_selectors
+            .into_iter()
+            .map(|diagnostic| diagnostic.message)
+            .collect()
+    }
+
+    #[test]
+    fn allows_valid_rules() {
+        let items = vec![
+            quote!("import { style, recipe } from '@vanilla-extract/css';" as ModuleItem),
+            quote!(
+                "const root = style([base, {
+                    backgroundColor: 'red',
+                    'z-index': 1,
+                    WebkitTapHighlightColor: 'transparent',
+                    '--gap': '4px',
+                    vars: { [gap]: '2px' },
+                    ':hover': { color: 'blue' },
+                    selectors: { '&:focus': { outline: 'none' } },
+                    '@media': { 'screen and (min-width: 768px)': { padding: 4 } },
+                }]);" as ModuleItem
+            ),
+            quote!(
+                "const button = recipe({
+                    base: { display: 'flex' },
+                    variants: { size: { small: { fontSize: 12 } } },
+                    compoundVariants: [{ variants: { size: 'small' }, style: { stroke: 'red' } }],
+                });" as ModuleItem
+            ),
+        ];
+
+        assert_eq!(find(items), Vec::<String>::new());
+    }
+
+    #[test]
+    fn finds_invalid_keys() {
+        let items = vec![
+            quote!(
+                "import { style, globalStyle, styleVariants, recipe } from '@vanilla-extract/css';"
+                    as ModuleItem
+            ),
+            quote!(
+                "const root = style({ backgroundColour: 'red', '@media screen': {} });"
+                    as ModuleItem
+            ),
+            quote!(
+                "globalStyle('html', { ':hover': { colr: 'red' }, '@page': {} });" as ModuleItem
+            ),
+            quote!("const sizes = styleVariants({ small: { paddin: 2 } });" as ModuleItem),
+            quote!(
+                "const mapped = styleVariants(sizes, (size) => ({ padding: size }));" as ModuleItem
+            ),
+            quote!(
+                "const button = recipe({ variants: { tone: { dark: { foo: 1 } } } });"
+                    as ModuleItem
+            ),
+        ];
+
+        assert_eq!(
+            find(items),
+            vec![
+                "Unknown CSS property `backgroundColour`, did you mean `backgroundColor`?",
+                "`@media screen` must be nested under `'@media'`, i.e `'@media': { 'screen': { ... } }`",
+                "Unknown CSS property `colr`, did you mean `color`?",
+                "Unknown at-rule `@page`, style rules only support `@media`, `@supports`, `@container` and `@layer`",
+                "Unknown CSS property `paddin`, did you mean `padding`?",
+                "Unknown CSS property `foo`",
+            ]
+        );
+    }
+
+    #[test]
+    fn suggests_fixes() {
+        let items = vec![
+            quote!("import { style } from '@vanilla-extract/css';" as ModuleItem),
+            quote!(
+                "const root = style({ colr: 'red', 'paddin': 0, heigt, foo: 1 });" as ModuleItem
+            ),
+        ];
+        let analysis = ModuleAnalysis::analyze(&items);
+
+        assert_eq!(
+            validate_style_rules(&items, &analysis)
+                .unknown_keys
+                .into_iter()
+                .map(|diagnostic| diagnostic.fix.map(|fix| fix.edits[0].replacement.clone()))
+                .collect::<Vec<_>>(),
+            vec![
+                Some("color".to_string()),
+                Some("padding".to_string()),
+                Some("height: heigt".to_string()),
+                None,
+            ]
+        );
+    }
+
+    #[test]
+    fn finds_untargeted_selectors() {
+        let items = vec![
+            quote!("import { style } from '@vanilla-extract/css';" as ModuleItem),
+            quote!(
+                "const root = style({
+                    selectors: {
+                        '&:hover, .parent &': { color: 'red' },
+                        [`${parent} > &`]: { color: 'blue' },
+                        '&:focus, & .child': { color: 'green' },
+                        [`${parent} .child`]: { color: 'white' },
+                    },
+                });" as ModuleItem
+            ),
+        ];
+
+        assert_eq!(
+            find_selectors(items),
+            vec![
+                "Selector `& .child` does not target `&`, selectors of a style can only target \
+                 the element it's applied to",
+                "Selector `${parent} .child` does not target `&`, selectors of a style can only \
+                 target the element it's applied to",
+            ]
+        );
+    }
+}
@@ -0,0 +1,324 @@
+use std::collections::HashMap;
+
+use swc_core::{
+    common::{Span, Spanned},
+    ecma::{
+        ast::{
+            CallExpr, Decl, Expr, ModuleDecl, ModuleItem, ObjectLit, Pat, Prop, PropOrSpread, Stmt,
+        },
+        atoms::JsWord,
+        visit::{Visit, VisitWith},
+    },
+};
+
+use crate::{
+    analysis::ModuleAnalysis, get_relavant_call::get_relavant_call, prop_name::get_prop_name,
+    unwrap_expr::unwrap_expr,
+};
+
+/// Token of a contract or theme object literal, with the value if it's written as a key-value.
+struct Token<'a> {
+    key: String,
+    span: Span,
+    value: Option<&'a Expr>,
+}
+
+/// Returns the tokens of given object literal, along with whether all of them are known, which is
+/// not the case if the object has spreads or computed keys.
+fn get_tokens(object_lit: &ObjectLit) -> (Vec<Token<'_>>, bool) {
+    let mut tokens = vec![];
+    let mut is_complete = true;
+
+    for prop in &object_lit.props {
+        let prop = match prop {
+            PropOrSpread::Prop(prop) => prop,
+            PropOrSpread::Spread(..) => {
+                is_complete = false;
+                continue;
+            }
+        };
+
+        let token = match &**prop {
+            Prop::KeyValue(key_value) => get_prop_name(&key_value.key).map(|key| Token {
+                key,
+                span: key_value.key.span(),
+                value: Some(&*key_value.value),
+            }),
+            // `{ color }`
+            Prop::Shorthand(ident) => Some(Token {
+                key: ident.sym.to_string(),
+                span: ident.span,
+                value: None,
+            }),
+            _ => None,
+        };
+        match token {
+            Some(token) => tokens.push(token),
+            None => is_complete = false,
+        }
+    }
+
+    (tokens, is_complete)
+}
+
+/// Resolves contracts and token objects referred to by name.
+struct ThemeResolver<'a> {
+    analysis: &'a ModuleAnalysis,
+    /// Initializers of top level bindings.
+    bindings: HashMap<&'a JsWord, &'a Expr>,
+}
+
+impl<'a> ThemeResolver<'a> {
+    /// Resolves given expression to an object literal, following top level bindings.
+    fn resolve_object<'b>(&'b self, expr: &'b Expr) -> Option<&'b ObjectLit> {
+        match unwrap_expr(expr) {
+            Expr::Object(object_lit) => Some(object_lit),
+            Expr::Ident(ident) => match unwrap_expr(self.bindings.get(&ident.sym)?) {
+                Expr::Object(object_lit) => Some(object_lit),
+                _ => None,
+            },
+            _ => None,
+        }
+    }
+
+    /// Returns the name and the shape of the contract given expression refers to, if it's created
+    /// by `createThemeContract` in the file.
+    fn resolve_contract<'b>(&'b self, expr: &'b Expr) -> Option<(&'b JsWord, &'b ObjectLit)> {
+        let ident = match unwrap_expr(expr) {
+            Expr::Ident(ident) => ident,
+            _ => return None,
+        };
+        let call_expr = match unwrap_expr(self.bindings.get(&ident.sym)?) {
+            Expr::Call(call_expr) => call_expr,
+            _ => return None,
+        };
+        let used_export = get_relavant_call(
+            call_expr,
+            &self.analysis.namespace_import,
+            &self.analysis.import_identifiers,
+        )?;
+        if used_export != "createThemeContract" {
+            return None;
+        }
+
+        let shape = self.resolve_object(&call_expr.args.first()?.expr)?;
+        Some((&ident.sym, shape))
+    }
+
+    /// Compares the tokens of a theme object with the ones of the contract object at given path.
+    fn compare(
+        &self,
+        contract_name: &str,
+        contract: &ObjectLit,
+        theme: &ObjectLit,
+        path: &str,
+        mismatches: &mut Vec<(String, Span)>,
+    ) {
+        let (contract_tokens, is_contract_complete) = get_tokens(contract);
+        let (theme_tokens, is_theme_complete) = get_tokens(theme);
+        let token_path = |key: &str| {
+            if path.is_empty() {
+                key.to_string()
+            } else {
+                format!("{}.{}", path, key)
+            }
+        };
+
+        if is_theme_complete {
+            for contract_token in &contract_tokens {
+                if !theme_tokens
+                    .iter()
+                    .any(|token| token.key == contract_token.key)
+                {
+                    mismatches.push((
+                        format!(
+                            "Token `{}` of contract `{}` is missing",
+                            token_path(&contract_token.key),
+                            contract_name
+                        ),
+                        theme.span,
+                    ));
+                }
+            }
+        }
+
+        for theme_token in &theme_tokens {
+            let contract_token = contract_tokens
+                .iter()
+                .find(|token| token.key == theme_token.key);
+
+            match (contract_token, theme_token.value) {
+                (Some(contract_token), Some(value)) => {
+                    let nested_objects = contract_token
+                        .value
+                        .and_then(|contract_value| self.resolve_object(contract_value))
+                        .zip(self.resolve_object(value));
+                    if let Some((nested_contract, nested_theme)) = nested_objects {
+                        self.compare(
+                            contract_name,
+                            nested_contract,
+                            nested_theme,
+                            &token_path(&theme_token.key),
+                            mismatches,
+                        );
+                    }
+                }
+                (Some(..), None) => {}
+                (None, _) if is_contract_complete => {
+                    mismatches.push((
+                        format!(
+                            "Token `{}` is not part of contract `{}`",
+                            token_path(&theme_token.key),
+                            contract_name
+                        ),
+                        theme_token.span,
+                    ));
+                }
+                (None, _) => {}
+            }
+        }
+    }
+}
+
+/// Compares the token objects given to `createTheme` and `createGlobalTheme` with the shape of
+/// the contract they implement, when the contract is created by `createThemeContract` in the same
+/// file.
+struct ThemeContractValidator<'a> {
+    resolver: ThemeResolver<'a>,
+
+    mismatches: Vec<(String, Span)>,
+}
+
+impl<'a> Visit for ThemeContractValidator<'a> {
+    fn visit_call_expr(&mut self, call_expr: &CallExpr) {
+        let used_export = get_relavant_call(
+            call_expr,
+            &self.resolver.analysis.namespace_import,
+            &self.resolver.analysis.import_identifiers,
+        );
+        // `createTheme(contract, tokens)`, `createGlobalTheme(selector, contract, tokens)`
+        let contract_position = match used_export.as_deref() {
+            Some("createTheme") => Some(0),
+            Some("createGlobalTheme") => Some(1),
+            _ => None,
+        };
+
+        if let Some(contract_position) = contract_position {
+            let args = (
+                call_expr.args.get(contract_position),
+                call_expr.args.get(contract_position + 1),
+            );
+            if let (Some(contract_arg), Some(theme_arg)) = args {
+                if let (Some((contract_name, contract)), Some(theme)) = (
+                    self.resolver.resolve_contract(&contract_arg.expr),
+                    self.resolver.resolve_object(&theme_arg.expr),
+                ) {
+                    self.resolver
+                        .compare(contract_name, contract, theme, "", &mut self.mismatches);
+                }
+            }
+        }
+
+        call_expr.visit_children_with(self);
+    }
+}
+
+/// Returns the declaration of given top level item, exported or not.
+fn get_decl(item: &ModuleItem) -> Option<&Decl> {
+    match item {
+        ModuleItem::Stmt(Stmt::Decl(decl)) => Some(decl),
+        ModuleItem::ModuleDecl(ModuleDecl::ExportDecl(export_decl)) => Some(&export_decl.decl),
+        _ => None,
+    }
+}
+
+/// Returns missing and unexpected tokens of themes implementing a contract created in the same
+/// file, with the message to report them with.
+pub fn find_theme_contract_mismatches(
+    items: &[ModuleItem],
+    analysis: &ModuleAnalysis,
+) -> Vec<(String, Span)> {
+    let bindings = items
+        .iter()
+        .filter_map(get_decl)
+        .filter_map(|decl| match decl {
+            Decl::Var(var_decl) => Some(var_decl),
+            _ => None,
+        })
+        .flat_map(|var_decl| &var_decl.decls)
+        .filter_map(|declarator| match (&declarator.name, &declarator.init) {
+            (Pat::Ident(ident), Some(init)) => Some((&ident.id.sym, &**init)),
+            _ => None,
+        })
+        .collect();
+
+    let mut validator = ThemeContractValidator {
+        resolver: ThemeResolver { analysis, bindings },
+        mismatches: vec![],
+    };
+    for item in items {
+        item.visit_with(&mut validator);
+    }
+
+    validator.mismatches
+}
+
+#[cfg(test)]
+mod tests {
+    use pretty_assertions::assert_eq;
+    use swc_core::quote;
+
+    use super::*;
+
+    fn find(items: Vec<ModuleItem>) -> Vec<String> {
+        let analysis = ModuleAnalysis::analyze(&items);
+        find_theme_contract_mismatches(&items, &analysis)
+            .into_iter()
+            .map(|(message, _)| message)
+            .collect()
+    }
+
+    #[test]
+    fn allows_matching_themes() {
+        let items = vec![
+            quote!(
+                "import { createThemeContract, createTheme } from '@vanilla-extract/css';"
+                    as ModuleItem
+            ),
+            quote!(
+                "const vars = createThemeContract({ color: { brand: null }, space: null });"
+                    as ModuleItem
+            ),
+            quote!("const brand = { brand: 'red' };" as ModuleItem),
+            quote!(
+                "const theme = createTheme(vars, { color: brand, space: '4px' });" as ModuleItem
+            ),
+            quote!("const partial = createTheme(vars, { ...base, space: '4px' });" as ModuleItem),
+            quote!("const other = createTheme(external, { anything: 1 });" as ModuleItem),
+        ];
+
+        assert_eq!(find(items), Vec::<String>::new());
+    }
+
+    #[test]
+    fn finds_mismatches() {
+        let items = vec![
+            quote!(
+                "import { createThemeContract, createTheme, createGlobalTheme } from '@vanilla-extract/css';"
+                    as ModuleItem
+            ),
+            quote!("const vars = createThemeContract({ color: { brand: null, text: null }, space: null });" as ModuleItem),
+            quote!("const theme = createTheme(vars, { color: { brand: 'red', link: 'blue' }, space: '4px' });" as ModuleItem),
+            quote!("createGlobalTheme(':root', vars, { color: { brand: 'red', text: 'black' } });" as ModuleItem),
+        ];
+
+        assert_eq!(
+            find(items),
+            vec![
+                "Token `color.text` of contract `vars` is missing",
+                "Token `color.link` is not part of contract `vars`",
+                "Token `space` of contract `vars` is missing",
+            ]
+        );
+    }
+}
@@ -0,0 +1,188 @@
+use swc_core::{
+    common::Span,
+    ecma::{
+        ast::{Callee, Decl, Expr, ModuleItem, ObjectPatProp, Pat, Stmt},
+        atoms::JsWord,
+        visit::VisitWith,
+    },
+};
+
+use crate::{
+    analysis::ModuleAnalysis, constants::DEBUGGABLE_FUNCTION_CONFIG,
+    get_relavant_call::get_relavant_call, reference_collect_visitor::ReferenceCollectVisitor,
+    unwrap_expr::unwrap_expr,
+};
+
+/// Debuggable functions which only generate a name, without registering any CSS.
+static NAME_ONLY_FUNCTIONS: [&str; 2] = ["createVar", "createContainer"];
+
+/// A top level declaration of styles, none of whose bindings is read or exported.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct UnusedStyle {
+    /// Name of the first binding declared.
+    pub name: String,
+    /// Function called to create the styles, i.e `style` or a local wrapper.
+    pub used_export: String,
+    /// Span of the declarator.
+    pub span: Span,
+}
+
+/// Returns the function given expression calls if it registers styles, either a debuggable
+/// vanilla-extract function or a local wrapper of one.
+fn get_style_call(expr: &Expr, analysis: &ModuleAnalysis) -> Option<String> {
+    let call_expr = match unwrap_expr(expr) {
+        Expr::Call(call_expr) => call_expr,
+        _ => return None,
+    };
+
+    if let Some(used_export) = get_relavant_call(
+        call_expr,
+        &analysis.namespace_import,
+        &analysis.import_identifiers,
+    ) {
+        let registers_styles = DEBUGGABLE_FUNCTION_CONFIG.contains_key(&used_export)
+            && !NAME_ONLY_FUNCTIONS.contains(&used_export.as_str());
+        return registers_styles.then_some(used_export);
+    }
+
+    match &call_expr.callee {
+        Callee::Expr(callee) => match &**callee {
+            Expr::Ident(ident) if analysis.wrappers.contains_key(&ident.to_id()) => {
+                Some(ident.sym.to_string())
+            }
+            _ => None,
+        },
+        _ => None,
+    }
+}
+
+/// Returns the bindings declared by given pattern, i.e both names of
+/// `const [themeClass, vars] = createTheme()` or `const { primary, secondary } = styleVariants()`.
+fn get_binding_names(pat: &Pat) -> Vec<&JsWord> {
+    match pat {
+        Pat::Ident(ident) => vec![&ident.id.sym],
+        Pat::Array(array_pat) => array_pat
+            .elems
+            .iter()
+            .flatten()
+            .flat_map(get_binding_names)
+            .collect(),
+        Pat::Object(object_pat) => object_pat
+            .props
+            .iter()
+            .flat_map(|prop| match prop {
+                ObjectPatProp::KeyValue(key_value) => get_binding_names(&key_value.value),
+                ObjectPatProp::Assign(assign) => vec![&assign.key.sym],
+                ObjectPatProp::Rest(rest) => get_binding_names(&rest.arg),
+            })
+            .collect(),
+        Pat::Assign(assign_pat) => get_binding_names(&assign_pat.left),
+        Pat::Rest(rest_pat) => get_binding_names(&rest_pat.arg),
+        _ => vec![],
+    }
+}
+
+/// Finds top level declarations initialized with a call registering styles, whose bindings are
+/// neither read nor exported. Their CSS is registered and shipped even though nothing uses it.
+/// Functions called for their side effects, like `globalStyle`, are not bound to a name and are
+/// never reported.
+///
+/// Only `var`, `let` and `const` declarations which are not exported inline are checked, with
+/// any binding of a destructuring pattern counting as a use of the whole declaration. Styles
+/// assigned to existing bindings or object properties are not tracked.
+pub fn find_unused_styles(items: &[ModuleItem], analysis: &ModuleAnalysis) -> Vec<UnusedStyle> {
+    let mut reference_collect_visitor = ReferenceCollectVisitor::default();
+    for item in items {
+        item.visit_with(&mut reference_collect_visitor);
+    }
+
+    items
+        .iter()
+        .filter_map(|item| match item {
+            ModuleItem::Stmt(Stmt::Decl(Decl::Var(var_decl))) => Some(var_decl),
+            _ => None,
+        })
+        .flat_map(|var_decl| &var_decl.decls)
+        .filter_map(|declarator| {
+            let used_export = get_style_call(declarator.init.as_deref()?, analysis)?;
+            let names = get_binding_names(&declarator.name);
+            let is_used = names.iter().any(|name| {
+                reference_collect_visitor.is_referenced(name)
+                    || analysis.exports.contains_key(*name)
+            });
+            if is_used {
+                return None;
+            }
+
+            Some(UnusedStyle {
+                name: names.first()?.to_string(),
+                used_export,
+                span: declarator.span,
+            })
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use pretty_assertions::assert_eq;
+    use swc_core::quote;
+
+    use super::*;
+
+    fn find(items: Vec<ModuleItem>) -> Vec<(String, String)> {
+        let analysis = ModuleAnalysis::analyze(&items);
+        find_unused_styles(&items, &analysis)
+            .into_iter()
+            .map(|unused_style| (unused_style.name, unused_style.used_export))
+            .collect()
+    }
+
+    #[test]
+    fn allows_used_styles() {
+        let items = vec![
+            quote!(
+                "import { style, styleVariants, createTheme, globalStyle } from '@vanilla-extract/css';"
+                    as ModuleItem
+            ),
+            quote!("const base = style({});" as ModuleItem),
+            quote!("const root = style([base, {}]);" as ModuleItem),
+            quote!("const [themeClass, vars] = createTheme({});" as ModuleItem),
+            quote!("globalStyle(`${themeClass} a`, { color: vars.color });" as ModuleItem),
+            quote!("const card = style({});" as ModuleItem),
+            quote!("let { primary, ...rest } = styleVariants({});" as ModuleItem),
+            quote!("export { root, card as panel, rest };" as ModuleItem),
+        ];
+
+        assert_eq!(find(items), vec![]);
+    }
+
+    #[test]
+    fn finds_unused_styles() {
+        let items = vec![
+            quote!(
+                "import { style, styleVariants, createVar, recipe } from '@vanilla-extract/css';"
+                    as ModuleItem
+            ),
+            quote!("const card = (rule, id) => style(rule, id);" as ModuleItem),
+            quote!("const root = style({});" as ModuleItem),
+            quote!("const button = recipe({});" as ModuleItem),
+            quote!("const panel = card({});" as ModuleItem),
+            quote!("const color = createVar();" as ModuleItem),
+            quote!("let header = style({});" as ModuleItem),
+            quote!("const { primary, secondary } = styleVariants({});" as ModuleItem),
+            quote!("export const used = style({});" as ModuleItem),
+        ];
+
+        assert_eq!(
+            find(items),
+            vec![
+                ("root".to_string(), "style".to_string()),
+                ("button".to_string(), "recipe".to_string()),
+                ("panel".to_string(), "card".to_string()),
+                ("header".to_string(), "style".to_string()),
+                ("primary".to_string(), "styleVariants".to_string()),
+            ]
+        );
+    }
+}
@@ -0,0 +1,15 @@
+use swc_core::ecma::ast::Expr;
+
+/// Returns given expr without wrapping parens, TypeScript assertions, `satisfies` or non-null
+/// assertions, none of which change the value at runtime.
+pub(crate) fn unwrap_expr(expr: &Expr) -> &Expr {
+    match expr {
+        Expr::Paren(paren_expr) => unwrap_expr(&paren_expr.expr),
+        Expr::TsAs(ts_as_expr) => unwrap_expr(&ts_as_expr.expr),
+        Expr::TsConstAssertion(ts_const_assertion) => unwrap_expr(&ts_const_assertion.expr),
+        Expr::TsTypeAssertion(ts_type_assertion) => unwrap_expr(&ts_type_assertion.expr),
+        Expr::TsSatisfies(ts_satisfies_expr) => unwrap_expr(&ts_satisfies_expr.expr),
+        Expr::TsNonNull(ts_non_null_expr) => unwrap_expr(&ts_non_null_expr.expr),
+        _ => expr,
+    }
+}
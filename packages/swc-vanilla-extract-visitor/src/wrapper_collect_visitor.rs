@@ -0,0 +1,177 @@
+use std::collections::HashMap;
+
+use swc_core::ecma::{
+    ast::{
+        ArrowExpr, BlockStmtOrExpr, CallExpr, Expr, FnDecl, Function, Id, Ident, Pat, VarDeclarator,
+    },
+    visit::{Visit, VisitWith},
+};
+
+use crate::{
+    constants::DEBUGGABLE_FUNCTION_CONFIG,
+    get_relavant_call::{get_relavant_call, get_wrapper_position},
+    unwrap_expr::unwrap_expr,
+};
+
+/// Returns the bindings of the parameters, `None` for the ones which are not plain bindings.
+fn get_param_ids<'a>(params: impl Iterator<Item = &'a Pat>) -> Vec<Option<Id>> {
+    params
+        .map(|pat| match pat {
+            Pat::Ident(ident) => Some(ident.id.to_id()),
+            // `id = 'default'`
+            Pat::Assign(assign_pat) => match &*assign_pat.left {
+                Pat::Ident(ident) => Some(ident.id.to_id()),
+                _ => None,
+            },
+            _ => None,
+        })
+        .collect()
+}
+
+/// Finds the parameter of the function forwarded as the debug id of a debuggable call in its body.
+struct ForwardedParamVisitor<'a> {
+    params: Vec<Option<Id>>,
+    namespace_import: &'a Option<Ident>,
+    import_identifiers: &'a HashMap<Ident, String>,
+    wrappers: &'a HashMap<Id, usize>,
+
+    position: Option<usize>,
+}
+
+impl<'a> ForwardedParamVisitor<'a> {
+    /// Returns the position of the parameter passed as given argument, if any.
+    fn get_param_position(&self, call_expr: &CallExpr, arg_index: usize) -> Option<usize> {
+        let arg = call_expr.args.get(arg_index)?;
+        if arg.spread.is_some() {
+            return None;
+        }
+
+        match &*arg.expr {
+            Expr::Ident(ident) => {
+                let id = ident.to_id();
+                self.params
+                    .iter()
+                    .position(|param| param.as_ref() == Some(&id))
+            }
+            _ => None,
+        }
+    }
+}
+
+impl<'a> Visit for ForwardedParamVisitor<'a> {
+    fn visit_call_expr(&mut self, call_expr: &CallExpr) {
+        if self.position.is_some() {
+            return;
+        }
+
+        if let Some(used_export) =
+            get_relavant_call(call_expr, self.namespace_import, self.import_identifiers)
+        {
+            // Debug id is the last argument of the style functions
+            if let Some(max_params) = DEBUGGABLE_FUNCTION_CONFIG.get(&used_export) {
+                if call_expr.args.len() == *max_params {
+                    self.position = self.get_param_position(call_expr, max_params - 1);
+                }
+            }
+        } else if let Some(position) = get_wrapper_position(call_expr, self.wrappers) {
+            self.position = self.get_param_position(call_expr, position);
+        }
+
+        if self.position.is_none() {
+            call_expr.visit_children_with(self);
+        }
+    }
+}
+
+/// A visitor to collect functions declared in the file which forward one of their parameters as
+/// the debug id of a vanilla-extract style function, i.e
+///
+/// ```ts
+/// const card = (rule: StyleRule, id?: string) => style([base, rule], id);
+/// ```
+///
+/// Calls to these functions are debuggable at the position of the forwarded parameter. Wrappers
+/// are keyed by binding, so calls to a local or parameter shadowing one are left alone.
+pub struct WrapperCollectVisitor<'a> {
+    /// Position of the forwarded parameter for each wrapper function binding.
+    pub wrappers: HashMap<Id, usize>,
+
+    namespace_import: &'a Option<Ident>,
+    import_identifiers: &'a HashMap<Ident, String>,
+}
+
+impl<'a> WrapperCollectVisitor<'a> {
+    pub fn new(
+        namespace_import: &'a Option<Ident>,
+        import_identifiers: &'a HashMap<Ident, String>,
+    ) -> Self {
+        Self {
+            wrappers: Default::default(),
+
+            namespace_import,
+            import_identifiers,
+        }
+    }
+
+    fn collect<N: for<'b> VisitWith<ForwardedParamVisitor<'b>>>(
+        &mut self,
+        id: Id,
+        params: Vec<Option<Id>>,
+        body: &N,
+    ) {
+        if self.wrappers.contains_key(&id) {
+            return;
+        }
+
+        let mut forwarded_param_visitor = ForwardedParamVisitor {
+            params,
+            namespace_import: self.namespace_import,
+            import_identifiers: self.import_identifiers,
+            wrappers: &self.wrappers,
+
+            position: None,
+        };
+        body.visit_with(&mut forwarded_param_visitor);
+
+        if let Some(position) = forwarded_param_visitor.position {
+            self.wrappers.insert(id, position);
+        }
+    }
+
+    fn collect_function(&mut self, ident: &Ident, function: &Function) {
+        if let Some(body) = &function.body {
+            let params = get_param_ids(function.params.iter().map(|param| &param.pat));
+            self.collect(ident.to_id(), params, body);
+        }
+    }
+
+    fn collect_arrow(&mut self, ident: &Ident, arrow_expr: &ArrowExpr) {
+        let params = get_param_ids(arrow_expr.params.iter());
+        match &arrow_expr.body {
+            BlockStmtOrExpr::BlockStmt(block_stmt) => {
+                self.collect(ident.to_id(), params, block_stmt)
+            }
+            BlockStmtOrExpr::Expr(expr) => self.collect(ident.to_id(), params, expr),
+        }
+    }
+}
+
+impl<'a> Visit for WrapperCollectVisitor<'a> {
+    fn visit_fn_decl(&mut self, fn_decl: &FnDecl) {
+        fn_decl.visit_children_with(self);
+
+        self.collect_function(&fn_decl.ident, &fn_decl.function);
+    }
+
+    fn visit_var_declarator(&mut self, var_declarator: &VarDeclarator) {
+        var_declarator.visit_children_with(self);
+
+        if let (Pat::Ident(ident), Some(init)) = (&var_declarator.name, &var_declarator.init) {
+            match unwrap_expr(init) {
+                Expr::Arrow(arrow_expr) => self.collect_arrow(&ident.id, arrow_expr),
+                Expr::Fn(fn_expr) => self.collect_function(&ident.id, &fn_expr.function),
+                _ => {}
+            }
+        }
+    }
+}
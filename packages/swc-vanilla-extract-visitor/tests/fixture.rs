@@ -0,0 +1,87 @@
+use std::{
+    fs::read_to_string,
+    path::{Path, PathBuf},
+    sync::Arc,
+};
+
+use serde_json::Value;
+use swc_core::{
+    common::{chain, Mark},
+    ecma::{
+        parser::{Syntax, TsConfig},
+        transforms::{
+            base::resolver,
+            testing::{test_fixture, FixtureTestConfig},
+        },
+        visit::as_folder,
+    },
+};
+use swc_vanilla_extract_testing::SharedSourceMap;
+use swc_vanilla_extract_visitor::{Config, VanillaExtractBuilder, VanillaExtractVisitor};
+
+/// Reads `config.json` next to the fixture input, using the same keys as the plugin options plus
+/// `filename`, the path of the file relative to the package directory.
+fn read_config(input: &Path) -> (String, VanillaExtractBuilder) {
+    let config =
+        read_to_string(input.with_file_name("config.json")).unwrap_or_else(|_| "null".to_string());
+
+    let filename = serde_json::from_str::<Value>(&config).expect("Config should be valid JSON")
+        ["filename"]
+        .as_str()
+        .unwrap_or("dir/mockFilename.css.ts")
+        .to_string();
+
+    let config = Config::from_json(&config).unwrap_or_else(|err| panic!("{}", err));
+    let builder = VanillaExtractBuilder::from(config).package_dir("/cwd");
+
+    (filename, builder)
+}
+
+#[testing::fixture("tests/fixture/**/input.ts")]
+fn fixture(input: PathBuf) {
+    let output = input.with_file_name("output.ts");
+    let (filename, builder) = read_config(&input);
+
+    test_fixture(
+        Syntax::Typescript(TsConfig::default()),
+        // SWC resolves the program before running plugins and custom transforms
+        &|t| {
+            chain!(
+                resolver(Mark::new(), Mark::new(), true),
+                builder.clone().build(
+                    Arc::new(SharedSourceMap(t.cm.clone())),
+                    (*t.comments).clone(),
+                    &filename,
+                )
+            )
+        },
+        &input,
+        &output,
+        FixtureTestConfig {
+            allow_error: true,
+            ..Default::default()
+        },
+    );
+}
+
+/// The deprecated constructor keeps the signature it had before the builder was added. It has no
+/// comments to emit the marker comment into.
+#[test]
+#[allow(deprecated)]
+fn deprecated_constructor() {
+    let input = PathBuf::from("tests/deprecated-constructor/input.ts");
+
+    test_fixture(
+        Syntax::Typescript(TsConfig::default()),
+        &|_| {
+            as_folder(VanillaExtractVisitor::new(
+                "dir/mockFilename.css.ts",
+                "swc-plugin-vanilla-extract",
+                "/cwd",
+            ))
+        },
+        &input,
+        &input.with_file_name("output.ts"),
+        Default::default(),
+    );
+}
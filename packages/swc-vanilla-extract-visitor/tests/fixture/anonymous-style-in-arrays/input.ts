@@ -0,0 +1,7 @@
+ import { style } from '@vanilla-extract/css';
+
+ export const height = [
+  style({
+    zIndex: 2,
+  })
+];
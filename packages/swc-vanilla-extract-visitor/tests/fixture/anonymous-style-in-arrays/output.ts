@@ -0,0 +1,9 @@
+/*@vanilla-extract/file-scope*/ import * as __vanilla_filescope__ from "@vanilla-extract/css/fileScope";
+__vanilla_filescope__.setFileScope("/cwd/dir/mockFilename.css.ts", "swc-plugin-vanilla-extract");
+import { style } from '@vanilla-extract/css';
+export const height = [
+    style({
+        zIndex: 2
+    }, "height")
+];
+__vanilla_filescope__.endFileScope();
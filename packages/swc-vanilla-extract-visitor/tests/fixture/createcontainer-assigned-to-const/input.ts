@@ -0,0 +1,3 @@
+import { createContainer } from '@vanilla-extract/css';
+
+const myContainer = createContainer();
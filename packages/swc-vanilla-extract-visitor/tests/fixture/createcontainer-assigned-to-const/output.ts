@@ -0,0 +1,5 @@
+/*@vanilla-extract/file-scope*/ import * as __vanilla_filescope__ from "@vanilla-extract/css/fileScope";
+__vanilla_filescope__.setFileScope("/cwd/dir/mockFilename.css.ts", "swc-plugin-vanilla-extract");
+import { createContainer } from '@vanilla-extract/css';
+const myContainer = createContainer("myContainer");
+__vanilla_filescope__.endFileScope();
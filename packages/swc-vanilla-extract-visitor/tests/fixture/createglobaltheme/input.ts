@@ -0,0 +1,3 @@
+import { createGlobalTheme } from '@vanilla-extract/css';
+
+const vars = createGlobalTheme(':root', { foo: 'bar' });
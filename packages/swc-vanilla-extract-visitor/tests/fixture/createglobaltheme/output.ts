@@ -0,0 +1,7 @@
+/*@vanilla-extract/file-scope*/ import * as __vanilla_filescope__ from "@vanilla-extract/css/fileScope";
+__vanilla_filescope__.setFileScope("/cwd/dir/mockFilename.css.ts", "swc-plugin-vanilla-extract");
+import { createGlobalTheme } from '@vanilla-extract/css';
+const vars = createGlobalTheme(':root', {
+    foo: 'bar'
+});
+__vanilla_filescope__.endFileScope();
@@ -0,0 +1,3 @@
+import { createTheme } from '@vanilla-extract/css';
+
+const darkTheme = createTheme({}, {});
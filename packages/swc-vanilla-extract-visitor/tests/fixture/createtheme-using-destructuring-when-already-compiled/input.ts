@@ -0,0 +1,6 @@
+import { createTheme } from '@vanilla-extract/css';
+
+var _createTheme = createTheme({}),
+  _createTheme2 = _slicedToArray(_createTheme, 2),
+  myThemeClass = _createTheme2[0],
+  vars = _createTheme2[1];
@@ -0,0 +1,5 @@
+/*@vanilla-extract/file-scope*/ import * as __vanilla_filescope__ from "@vanilla-extract/css/fileScope";
+__vanilla_filescope__.setFileScope("/cwd/dir/mockFilename.css.ts", "swc-plugin-vanilla-extract");
+import { createTheme } from '@vanilla-extract/css';
+var _createTheme = createTheme({}, "myThemeClass"), _createTheme2 = _slicedToArray(_createTheme, 2), myThemeClass = _createTheme2[0], vars = _createTheme2[1];
+__vanilla_filescope__.endFileScope();
@@ -0,0 +1,4 @@
+import { createTheme } from '@vanilla-extract/css';
+
+var _createTheme = _sliced_to_array(createTheme({}, {}), 2), myThemeClass = _createTheme[0], vars = _createTheme[1];
+var _a = __read(createTheme({}, {}), 2), otherThemeClass = _a[0], otherVars = _a[1];
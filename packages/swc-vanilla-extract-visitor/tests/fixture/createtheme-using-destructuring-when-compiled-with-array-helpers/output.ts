@@ -0,0 +1,6 @@
+/*@vanilla-extract/file-scope*/ import * as __vanilla_filescope__ from "@vanilla-extract/css/fileScope";
+__vanilla_filescope__.setFileScope("/cwd/dir/mockFilename.css.ts", "swc-plugin-vanilla-extract");
+import { createTheme } from '@vanilla-extract/css';
+var _createTheme = _sliced_to_array(createTheme({}, {}, "myThemeClass"), 2), myThemeClass = _createTheme[0], vars = _createTheme[1];
+var _a = __read(createTheme({}, {}, "otherThemeClass"), 2), otherThemeClass = _a[0], otherVars = _a[1];
+__vanilla_filescope__.endFileScope();
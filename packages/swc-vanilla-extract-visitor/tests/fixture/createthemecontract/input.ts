@@ -0,0 +1,5 @@
+import { createThemeContract } from '@vanilla-extract/css';
+
+const vars = createThemeContract({
+  foo: 'bar'
+});
@@ -0,0 +1,3 @@
+import { createVar } from '@vanilla-extract/css';
+
+const myVar = createVar();
@@ -0,0 +1,11 @@
+import { style } from '@vanilla-extract/css';
+
+const test = {
+  one: /* @ve-ignore */ style({ zIndex: 1 }),
+  two: style({ zIndex: 2 }),
+};
+
+/* @ve-debug-id primaryButton */
+export default style({ zIndex: 3 });
+
+const list = [/* @ve-ignore */ style({ zIndex: 4 })];
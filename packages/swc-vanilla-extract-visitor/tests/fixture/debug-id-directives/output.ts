@@ -0,0 +1,20 @@
+/*@vanilla-extract/file-scope*/ import * as __vanilla_filescope__ from "@vanilla-extract/css/fileScope";
+__vanilla_filescope__.setFileScope("/cwd/dir/mockFilename.css.ts", "swc-plugin-vanilla-extract");
+import { style } from '@vanilla-extract/css';
+const test = {
+    one: /* @ve-ignore */ style({
+        zIndex: 1
+    }),
+    two: style({
+        zIndex: 2
+    }, "test_two")
+};
+/* @ve-debug-id primaryButton */ export default style({
+    zIndex: 3
+}, "primaryButton");
+const list = [
+    /* @ve-ignore */ style({
+        zIndex: 4
+    })
+];
+__vanilla_filescope__.endFileScope();
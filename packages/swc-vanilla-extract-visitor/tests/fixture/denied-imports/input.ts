@@ -0,0 +1,10 @@
+import { style } from '@vanilla-extract/css';
+import type { ButtonProps } from './Button.tsx';
+import { Button } from './Button.tsx';
+import { ArrowIcon } from './icons/ArrowIcon.tsx';
+import { get } from 'lodash/get';
+import { vars } from './theme.css';
+
+export const root = style({
+  color: vars.color,
+});
@@ -0,0 +1,12 @@
+/*@vanilla-extract/file-scope*/ import * as __vanilla_filescope__ from "@vanilla-extract/css/fileScope";
+__vanilla_filescope__.setFileScope("/cwd/dir/mockFilename.css.ts", "swc-plugin-vanilla-extract");
+import { style } from '@vanilla-extract/css';
+import { ButtonProps } from './Button.tsx';
+import { Button } from './Button.tsx';
+import { ArrowIcon } from './icons/ArrowIcon.tsx';
+import { get } from 'lodash/get';
+import { vars } from './theme.css';
+export const root = style({
+    color: vars.color
+}, "root");
+__vanilla_filescope__.endFileScope();
@@ -0,0 +1,4 @@
+import * as css from '@vanilla-extract/css';
+
+var _css_styleVariants = css.styleVariants({}), primary = _css_styleVariants.primary, secondary = _css_styleVariants.secondary;
+var _css_createTheme = css.createTheme({}, {}), _css_createTheme2 = _slicedToArray(_css_createTheme, 2), vars = _css_createTheme2[1], myThemeClass = _css_createTheme2[0];
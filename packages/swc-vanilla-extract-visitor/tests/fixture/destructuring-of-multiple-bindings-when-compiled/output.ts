@@ -0,0 +1,6 @@
+/*@vanilla-extract/file-scope*/ import * as __vanilla_filescope__ from "@vanilla-extract/css/fileScope";
+__vanilla_filescope__.setFileScope("/cwd/dir/mockFilename.css.ts", "swc-plugin-vanilla-extract");
+import * as css from '@vanilla-extract/css';
+var _css_styleVariants = css.styleVariants({}, "primary_secondary"), primary = _css_styleVariants.primary, secondary = _css_styleVariants.secondary;
+var _css_createTheme = css.createTheme({}, {}, "myThemeClass"), _css_createTheme2 = _slicedToArray(_css_createTheme, 2), vars = _css_createTheme2[1], myThemeClass = _css_createTheme2[0];
+__vanilla_filescope__.endFileScope();
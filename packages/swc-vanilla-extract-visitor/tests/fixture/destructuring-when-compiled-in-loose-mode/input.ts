@@ -0,0 +1,4 @@
+import * as css from '@vanilla-extract/css';
+
+var _css_createTheme = css.createTheme({}, {}), myThemeClass = _css_createTheme[0], vars = _css_createTheme[1];
+var _css_styleVariants = css.styleVariants({}), primary = _css_styleVariants.primary;
@@ -0,0 +1,6 @@
+/*@vanilla-extract/file-scope*/ import * as __vanilla_filescope__ from "@vanilla-extract/css/fileScope";
+__vanilla_filescope__.setFileScope("/cwd/dir/mockFilename.css.ts", "swc-plugin-vanilla-extract");
+import * as css from '@vanilla-extract/css';
+var _css_createTheme = css.createTheme({}, {}, "myThemeClass"), myThemeClass = _css_createTheme[0], vars = _css_createTheme[1];
+var _css_styleVariants = css.styleVariants({}, "primary"), primary = _css_styleVariants.primary;
+__vanilla_filescope__.endFileScope();
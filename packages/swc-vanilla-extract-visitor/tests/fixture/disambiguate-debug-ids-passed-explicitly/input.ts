@@ -0,0 +1,5 @@
+import { style } from '@vanilla-extract/css';
+
+export const root = style({ zIndex: 1 });
+
+export const card = style({ zIndex: 2 }, 'root');
@@ -0,0 +1,10 @@
+/*@vanilla-extract/file-scope*/ import * as __vanilla_filescope__ from "@vanilla-extract/css/fileScope";
+__vanilla_filescope__.setFileScope("/cwd/dir/mockFilename.css.ts", "swc-plugin-vanilla-extract");
+import { style } from '@vanilla-extract/css';
+export const root = style({
+    zIndex: 1
+}, "root_2");
+export const card = style({
+    zIndex: 2
+}, 'root');
+__vanilla_filescope__.endFileScope();
@@ -0,0 +1,9 @@
+import { style } from '@vanilla-extract/css';
+
+export const button = {
+  primary_root: style({ zIndex: 1 }),
+  primary: { root: style({ zIndex: 2 }) },
+};
+
+/* @ve-debug-id button_primary_root */
+export const other = style({ zIndex: 3 });
@@ -0,0 +1,17 @@
+/*@vanilla-extract/file-scope*/ import * as __vanilla_filescope__ from "@vanilla-extract/css/fileScope";
+__vanilla_filescope__.setFileScope("/cwd/dir/mockFilename.css.ts", "swc-plugin-vanilla-extract");
+import { style } from '@vanilla-extract/css';
+export const button = {
+    primary_root: style({
+        zIndex: 1
+    }, "button_primary_root"),
+    primary: {
+        root: style({
+            zIndex: 2
+        }, "button_primary_root_2")
+    }
+};
+/* @ve-debug-id button_primary_root */ export const other = style({
+    zIndex: 3
+}, "button_primary_root_3");
+__vanilla_filescope__.endFileScope();
@@ -0,0 +1,5 @@
+import { style } from '@vanilla-extract/css';
+
+export const root = style({ zIndex: 1 });
+
+export default style({ zIndex: 2 });
@@ -0,0 +1,10 @@
+/*@vanilla-extract/file-scope*/ import * as __vanilla_filescope__ from "@vanilla-extract/css/fileScope";
+__vanilla_filescope__.setFileScope("/cwd/dir/button.css.ts", "swc-plugin-vanilla-extract");
+import { style } from '@vanilla-extract/css';
+export const root = style({
+    zIndex: 1
+}, "button_root_L3");
+export default style({
+    zIndex: 2
+}, "button_button_L5");
+__vanilla_filescope__.endFileScope();
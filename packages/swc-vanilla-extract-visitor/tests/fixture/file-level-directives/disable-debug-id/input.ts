@@ -0,0 +1,4 @@
+/* @ve-disable-debug-id */
+import { style } from '@vanilla-extract/css';
+
+const one = style({ zIndex: 1 });
@@ -0,0 +1,7 @@
+/* @ve-disable-debug-id */ /*@vanilla-extract/file-scope*/ import * as __vanilla_filescope__ from "@vanilla-extract/css/fileScope";
+__vanilla_filescope__.setFileScope("/cwd/dir/mockFilename.css.ts", "swc-plugin-vanilla-extract");
+import { style } from '@vanilla-extract/css';
+const one = style({
+    zIndex: 1
+});
+__vanilla_filescope__.endFileScope();
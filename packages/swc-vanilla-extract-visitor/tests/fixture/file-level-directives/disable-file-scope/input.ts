@@ -0,0 +1,4 @@
+// @ve-disable-file-scope
+import { style } from '@vanilla-extract/css';
+
+const one = style({ zIndex: 1 });
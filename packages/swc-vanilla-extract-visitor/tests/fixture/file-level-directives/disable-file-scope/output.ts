@@ -0,0 +1,5 @@
+// @ve-disable-file-scope
+import { style } from '@vanilla-extract/css';
+const one = style({
+    zIndex: 1
+}, "one");
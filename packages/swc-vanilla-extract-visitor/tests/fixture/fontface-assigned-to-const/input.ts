@@ -0,0 +1,5 @@
+import { fontFace } from '@vanilla-extract/css';
+
+const myFont = fontFace({
+  src: 'local("Comic Sans MS")',
+});
@@ -0,0 +1,6 @@
+import { globalKeyframes } from '@vanilla-extract/css';
+
+globalKeyframes('myKeyframes', {
+  from: { transform: 'rotate(0deg)' },
+  to: { transform: 'rotate(360deg)' }
+});
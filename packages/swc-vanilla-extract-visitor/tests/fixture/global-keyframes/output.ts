@@ -0,0 +1,12 @@
+/*@vanilla-extract/file-scope*/ import * as __vanilla_filescope__ from "@vanilla-extract/css/fileScope";
+__vanilla_filescope__.setFileScope("/cwd/dir/mockFilename.css.ts", "swc-plugin-vanilla-extract");
+import { globalKeyframes } from '@vanilla-extract/css';
+globalKeyframes('myKeyframes', {
+    from: {
+        transform: 'rotate(0deg)'
+    },
+    to: {
+        transform: 'rotate(360deg)'
+    }
+});
+__vanilla_filescope__.endFileScope();
@@ -0,0 +1,5 @@
+import { globalFontFace } from '@vanilla-extract/css';
+
+globalFontFace('myFont', {
+  src: 'local("Comic Sans MS")',
+});
@@ -0,0 +1,3 @@
+import { globalStyle } from '@vanilla-extract/css';
+
+globalStyle('html, body', { margin: 0 });
@@ -0,0 +1,7 @@
+/*@vanilla-extract/file-scope*/ import * as __vanilla_filescope__ from "@vanilla-extract/css/fileScope";
+__vanilla_filescope__.setFileScope("/cwd/dir/mockFilename.css.ts", "swc-plugin-vanilla-extract");
+import { globalStyle } from '@vanilla-extract/css';
+globalStyle('html, body', {
+    margin: 0
+});
+__vanilla_filescope__.endFileScope();
@@ -0,0 +1,16 @@
+/*@vanilla-extract/file-scope*/ import * as __vanilla_filescope__ from "@vanilla-extract/css/fileScope";
+__vanilla_filescope__.setFileScope("/cwd/dir/mockFilename.css.ts", "swc-plugin-vanilla-extract");
+import { style } from '@vanilla-extract/css';
+export const root = style({
+    zIndex: 1
+}, "root");
+__vanilla_filescope__.endFileScope();
+if (module.hot) {
+    module.hot.dispose(()=>{
+        if (typeof document !== 'undefined') {
+            document.querySelectorAll('style[data-file="/cwd/dir/mockFilename.css.ts"]').forEach((style)=>{
+                style.textContent = '';
+            });
+        }
+    });
+}
@@ -0,0 +1,7 @@
+const { setFileScope , endFileScope  } = require('@vanilla-extract/css/fileScope');
+setFileScope('src/dir/someFileName.css.ts', 'some-package');
+const { style  } = require('@vanilla-extract/css');
+const three = style({
+    zIndex: 2
+});
+endFileScope();
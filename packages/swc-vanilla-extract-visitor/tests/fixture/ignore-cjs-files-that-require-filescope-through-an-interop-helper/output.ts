@@ -0,0 +1,7 @@
+var _fileScope = _interop_require_wildcard(require('@vanilla-extract/css/fileScope'));
+(0, _fileScope.setFileScope)('src/dir/someFileName.css.ts', 'some-package');
+const { style  } = require('@vanilla-extract/css');
+const three = style({
+    zIndex: 2
+});
+(0, _fileScope.endFileScope)();
@@ -0,0 +1,8 @@
+/*@vanilla-extract/file-scope*/ import * as __vanilla_filescope__ from "@vanilla-extract/css/fileScope";
+__vanilla_filescope__.setFileScope("src/dir/someFileName.css.ts", "some-package");
+import { style } from '@vanilla-extract/css';
+
+const three = style({
+  zIndex: 2,
+}, "three");
+__vanilla_filescope__.endFileScope();
@@ -0,0 +1,10 @@
+var button_css_exports = {};
+__export(button_css_exports, {
+  root: () => root
+});
+module.exports = __toCommonJS(button_css_exports);
+var import_fileScope = require("@vanilla-extract/css/fileScope");
+var import_css = __toESM(require("@vanilla-extract/css"));
+(0, import_fileScope.setFileScope)("src/button.css.ts", "my-app");
+var root = (0, import_css.style)({ zIndex: 1 }, "root");
+(0, import_fileScope.endFileScope)();
@@ -0,0 +1,6 @@
+const __vi_ssr_import_0__ = await __vi_ssr_import__("@vanilla-extract/css/fileScope");
+__vi_ssr_import_0__.setFileScope("src/button.css.ts", "my-app");
+const __vi_ssr_import_1__ = await __vi_ssr_import__("@vanilla-extract/css");
+const root = __vi_ssr_import_1__.style({ zIndex: 1 }, "root");
+Object.defineProperty(__vi_ssr_exports__, "root", { enumerable: true, configurable: true, get(){ return root }});
+__vi_ssr_import_0__.endFileScope();
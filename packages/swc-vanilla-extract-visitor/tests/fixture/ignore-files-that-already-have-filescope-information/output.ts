@@ -0,0 +1,7 @@
+import { setFileScope, endFileScope } from '@vanilla-extract/css/fileScope';
+setFileScope('src/dir/someFileName.css.ts', 'some-package');
+import { style } from '@vanilla-extract/css';
+const three = style({
+    zIndex: 2
+});
+endFileScope();
@@ -0,0 +1,8 @@
+import { setFileScope, endFileScope } from '/node_modules/.vite/deps/@vanilla-extract_css_fileScope.js?v=1a2b3c';
+setFileScope('src/dir/someFileName.css.ts', 'some-package');
+import { style } from '@vanilla-extract/css';
+
+const three = style({
+  zIndex: 2,
+});
+endFileScope();
@@ -0,0 +1,5 @@
+/*@vanilla-extract/file-scope*/ const fileScope = loadFileScope();
+import { style } from '@vanilla-extract/css';
+const three = style({
+    zIndex: 2
+});
@@ -0,0 +1,11 @@
+import { style, styleVariants } from '@vanilla-extract/css';
+
+const three = style({
+    testStyle: {
+      zIndex: 2,
+    }
+}, 'myDebugValue');
+
+const four = styleVariants({
+  red: { color: 'red' }
+}, 'myDebugValue');
@@ -0,0 +1,14 @@
+/*@vanilla-extract/file-scope*/ import * as __vanilla_filescope__ from "@vanilla-extract/css/fileScope";
+__vanilla_filescope__.setFileScope("/cwd/dir/mockFilename.css.ts", "swc-plugin-vanilla-extract");
+import { style, styleVariants } from '@vanilla-extract/css';
+const three = style({
+    testStyle: {
+        zIndex: 2
+    }
+}, 'myDebugValue');
+const four = styleVariants({
+    red: {
+        color: 'red'
+    }
+}, 'myDebugValue', "four");
+__vanilla_filescope__.endFileScope();
@@ -0,0 +1,13 @@
+import { style } from '@vanilla-extract/css';
+
+const card = (rule, id) => style(rule, id);
+
+function panel(rule, options, debugId) {
+  return card({ ...rule, ...options }, debugId);
+}
+
+export const root = card({ zIndex: 1 });
+
+export const explicit = card({ zIndex: 2 }, 'explicit');
+
+export const box = panel({ zIndex: 3 });
@@ -0,0 +1,20 @@
+/*@vanilla-extract/file-scope*/ import * as __vanilla_filescope__ from "@vanilla-extract/css/fileScope";
+__vanilla_filescope__.setFileScope("/cwd/dir/mockFilename.css.ts", "swc-plugin-vanilla-extract");
+import { style } from '@vanilla-extract/css';
+const card = (rule, id)=>style(rule, id);
+function panel(rule, options, debugId) {
+    return card({
+        ...rule,
+        ...options
+    }, debugId);
+}
+export const root = card({
+    zIndex: 1
+}, "root");
+export const explicit = card({
+    zIndex: 2
+}, 'explicit');
+export const box = panel({
+    zIndex: 3
+}, undefined, "box");
+__vanilla_filescope__.endFileScope();
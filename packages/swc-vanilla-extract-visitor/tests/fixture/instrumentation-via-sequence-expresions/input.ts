@@ -0,0 +1,5 @@
+import { style } from '@vanilla-extract/css';
+
+const one = (something++, style({
+  zIndex: 1,
+}));
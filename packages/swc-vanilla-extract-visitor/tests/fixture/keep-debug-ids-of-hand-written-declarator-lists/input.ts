@@ -0,0 +1,5 @@
+import { recipe, styleVariants } from '@vanilla-extract/css';
+
+const button = recipe({}), variants = button.variants;
+var _card = styleVariants({}), primary = _card.primary;
+export { _card };
@@ -0,0 +1,7 @@
+/*@vanilla-extract/file-scope*/ import * as __vanilla_filescope__ from "@vanilla-extract/css/fileScope";
+__vanilla_filescope__.setFileScope("/cwd/dir/mockFilename.css.ts", "swc-plugin-vanilla-extract");
+import { recipe, styleVariants } from '@vanilla-extract/css';
+const button = recipe({}, "button"), variants = button.variants;
+var _card = styleVariants({}, "_card"), primary = _card.primary;
+export { _card };
+__vanilla_filescope__.endFileScope();
@@ -0,0 +1,6 @@
+import { keyframes } from '@vanilla-extract/css';
+
+const myAnimation = keyframes({
+  from: { transform: 'rotate(0deg)' },
+  to: { transform: 'rotate(360deg)' }
+});
@@ -0,0 +1,33 @@
+import { style, globalStyle } from '@vanilla-extract/css';
+
+const card = (rule, id) => style(rule, id);
+
+function panel(rule) {
+  return card(rule);
+}
+
+export const box = panel({ zIndex: 1 });
+
+export const colors = ['red', 'blue'].map((color) => style({ color }));
+
+(function () {
+  globalStyle('html', { zIndex: 2 });
+})();
+
+export function createStyle() {
+  return style({ zIndex: 3 });
+}
+
+function createLazyStyle() {
+  return style({ zIndex: 4 });
+}
+
+document.addEventListener('click', () => {
+  globalStyle('body', { zIndex: 5 });
+});
+
+export const theme = {
+  get root() {
+    return style({ zIndex: 6 });
+  },
+};
@@ -0,0 +1,44 @@
+/*@vanilla-extract/file-scope*/ import * as __vanilla_filescope__ from "@vanilla-extract/css/fileScope";
+__vanilla_filescope__.setFileScope("/cwd/dir/mockFilename.css.ts", "swc-plugin-vanilla-extract");
+import { style, globalStyle } from '@vanilla-extract/css';
+const card = (rule, id)=>style(rule, id);
+function panel(rule) {
+    return card(rule, "panel");
+}
+export const box = panel({
+    zIndex: 1
+});
+export const colors = [
+    'red',
+    'blue'
+].map((color)=>style({
+        color
+    }, "colors"));
+(function() {
+    globalStyle('html', {
+        zIndex: 2
+    });
+})();
+export function createStyle() {
+    return style({
+        zIndex: 3
+    }, "createStyle");
+}
+function createLazyStyle() {
+    return style({
+        zIndex: 4
+    }, "createLazyStyle");
+}
+document.addEventListener('click', ()=>{
+    globalStyle('body', {
+        zIndex: 5
+    });
+});
+export const theme = {
+    get root () {
+        return style({
+            zIndex: 6
+        }, "theme");
+    }
+};
+__vanilla_filescope__.endFileScope();
@@ -0,0 +1,6 @@
+import { style } from '@vanilla-extract/css';
+
+const test = {
+  one: style({ zIndex: 1 }),
+  two: style({ zIndex: 2 }),
+};
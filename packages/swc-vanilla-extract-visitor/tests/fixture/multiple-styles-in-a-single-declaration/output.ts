@@ -0,0 +1,12 @@
+/*@vanilla-extract/file-scope*/ import * as __vanilla_filescope__ from "@vanilla-extract/css/fileScope";
+__vanilla_filescope__.setFileScope("/cwd/dir/mockFilename.css.ts", "swc-plugin-vanilla-extract");
+import { style } from '@vanilla-extract/css';
+const test = {
+    one: style({
+        zIndex: 1
+    }, "test_one"),
+    two: style({
+        zIndex: 2
+    }, "test_two")
+};
+__vanilla_filescope__.endFileScope();
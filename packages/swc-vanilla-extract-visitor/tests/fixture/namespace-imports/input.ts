@@ -0,0 +1,5 @@
+import * as css from '@vanilla-extract/css';
+
+const one = css.style({
+    zIndex: 2,
+});
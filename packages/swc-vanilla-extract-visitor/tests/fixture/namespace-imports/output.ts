@@ -0,0 +1,7 @@
+/*@vanilla-extract/file-scope*/ import * as __vanilla_filescope__ from "@vanilla-extract/css/fileScope";
+__vanilla_filescope__.setFileScope("/cwd/dir/mockFilename.css.ts", "swc-plugin-vanilla-extract");
+import * as css from '@vanilla-extract/css';
+const one = css.style({
+    zIndex: 2
+}, "one");
+__vanilla_filescope__.endFileScope();
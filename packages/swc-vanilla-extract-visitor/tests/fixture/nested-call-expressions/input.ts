@@ -0,0 +1,13 @@
+import { style } from '@vanilla-extract/css';
+
+const one = instrument(style({
+  zIndex: 1,
+}));
+
+const two = instrument(instrument(style({
+  zIndex: 2,
+})));
+
+const three = instrument(instrument(instrument(style({
+  zIndex: 3,
+}))));
@@ -0,0 +1,13 @@
+/*@vanilla-extract/file-scope*/ import * as __vanilla_filescope__ from "@vanilla-extract/css/fileScope";
+__vanilla_filescope__.setFileScope("/cwd/dir/mockFilename.css.ts", "swc-plugin-vanilla-extract");
+import { style } from '@vanilla-extract/css';
+const one = instrument(style({
+    zIndex: 1
+}, "one"));
+const two = instrument(instrument(style({
+    zIndex: 2
+}, "two")));
+const three = instrument(instrument(instrument(style({
+    zIndex: 3
+}, "three"))));
+__vanilla_filescope__.endFileScope();
@@ -0,0 +1,10 @@
+import { style, styleVariants } from '@vanilla-extract/css';
+
+/* @ve-debug-id primaryButton */
+export const button = [style({ zIndex: 1 }), style({ zIndex: 2 })];
+
+// @ve-ignore
+export const variants = styleVariants({
+  primary: style({ zIndex: 3 }),
+  secondary: style({ zIndex: 4 }),
+});
@@ -0,0 +1,21 @@
+/*@vanilla-extract/file-scope*/ import * as __vanilla_filescope__ from "@vanilla-extract/css/fileScope";
+__vanilla_filescope__.setFileScope("/cwd/dir/mockFilename.css.ts", "swc-plugin-vanilla-extract");
+import { style, styleVariants } from '@vanilla-extract/css';
+/* @ve-debug-id primaryButton */ export const button = [
+    style({
+        zIndex: 1
+    }, "button"),
+    style({
+        zIndex: 2
+    }, "button_2")
+];
+// @ve-ignore
+export const variants = styleVariants({
+    primary: style({
+        zIndex: 3
+    }, "variants_primary"),
+    secondary: style({
+        zIndex: 4
+    }, "variants_secondary")
+});
+__vanilla_filescope__.endFileScope();
@@ -0,0 +1,20 @@
+import { style } from '@vanilla-extract/css';
+import { recipe } from '@vanilla-extract/recipes';
+
+export const root = style({ zIndex: 1 });
+
+export const button = recipe({ base: root });
+
+export const sizes = { small: 1, large: [2, 'large'] };
+
+export const createStyle = (zIndex: number) => style({ zIndex });
+
+export const handlers = { onClick() {} };
+
+export const cache = new Map();
+
+class Theme {}
+
+export { Theme, root as rootClass };
+
+export default function () {}
@@ -0,0 +1,29 @@
+/*@vanilla-extract/file-scope*/ import * as __vanilla_filescope__ from "@vanilla-extract/css/fileScope";
+__vanilla_filescope__.setFileScope("/cwd/dir/mockFilename.css.ts", "swc-plugin-vanilla-extract");
+import { style } from '@vanilla-extract/css';
+import { recipe } from '@vanilla-extract/recipes';
+export const root = style({
+    zIndex: 1
+}, "root");
+export const button = recipe({
+    base: root
+}, "button");
+export const sizes = {
+    small: 1,
+    large: [
+        2,
+        'large'
+    ]
+};
+export const createStyle = (zIndex: number)=>style({
+        zIndex
+    }, "createStyle");
+export const handlers = {
+    onClick () {}
+};
+export const cache = new Map();
+class Theme {
+}
+export { Theme, root as rootClass };
+export default function() {}
+__vanilla_filescope__.endFileScope();
@@ -0,0 +1,5 @@
+import { style } from 'some-other-package';
+
+const three = style({
+  zIndex: 2,
+});
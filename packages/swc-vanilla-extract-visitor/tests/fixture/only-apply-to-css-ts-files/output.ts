@@ -0,0 +1,4 @@
+import { style } from '@vanilla-extract/css';
+const three = style({
+    zIndex: 2
+});
@@ -0,0 +1,5 @@
+import { style } from '@vanilla-extract/css';
+
+const one = style({
+    zIndex: 2,
+});
@@ -0,0 +1,7 @@
+/*@vanilla-extract/file-scope*/ import * as __vanilla_filescope__ from "@vanilla-extract/css/fileScope";
+__vanilla_filescope__.setFileScope("/cwd/packages/button/src/button.css.ts", "@acme/button");
+import { style } from '@vanilla-extract/css';
+const one = style({
+    zIndex: 2
+}, "one");
+__vanilla_filescope__.endFileScope();
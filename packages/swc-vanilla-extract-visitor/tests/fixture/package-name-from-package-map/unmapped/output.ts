@@ -0,0 +1,7 @@
+/*@vanilla-extract/file-scope*/ import * as __vanilla_filescope__ from "@vanilla-extract/css/fileScope";
+__vanilla_filescope__.setFileScope("/cwd/dir/mockFilename.css.ts", "@acme/root");
+import { style } from '@vanilla-extract/css';
+const one = style({
+    zIndex: 2
+}, "one");
+__vanilla_filescope__.endFileScope();
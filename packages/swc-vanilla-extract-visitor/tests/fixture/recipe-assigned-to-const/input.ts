@@ -0,0 +1,3 @@
+import { recipe } from '@vanilla-extract/recipes';
+
+const button = recipe({});
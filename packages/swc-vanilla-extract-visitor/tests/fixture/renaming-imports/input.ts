@@ -0,0 +1,5 @@
+import { style as specialStyle } from '@vanilla-extract/css';
+
+const four = specialStyle({
+  zIndex: 2,
+});
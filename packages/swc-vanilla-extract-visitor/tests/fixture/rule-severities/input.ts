@@ -0,0 +1,8 @@
+import { style } from '@vanilla-extract/css';
+
+const base = style({ zIndex: 1 });
+
+export const root = [
+  style({ backgroundColour: 'red' }),
+  style({ color: 'blue' }),
+];
@@ -0,0 +1,15 @@
+/*@vanilla-extract/file-scope*/ import * as __vanilla_filescope__ from "@vanilla-extract/css/fileScope";
+__vanilla_filescope__.setFileScope("/cwd/dir/mockFilename.css.ts", "swc-plugin-vanilla-extract");
+import { style } from '@vanilla-extract/css';
+const base = style({
+    zIndex: 1
+}, "base");
+export const root = [
+    style({
+        backgroundColour: 'red'
+    }, "root"),
+    style({
+        color: 'blue'
+    }, "root_2")
+];
+__vanilla_filescope__.endFileScope();
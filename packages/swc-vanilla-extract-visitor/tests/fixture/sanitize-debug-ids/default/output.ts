@@ -0,0 +1,15 @@
+/*@vanilla-extract/file-scope*/ import * as __vanilla_filescope__ from "@vanilla-extract/css/fileScope";
+__vanilla_filescope__.setFileScope("/cwd/dir/mockFilename.css.ts", "swc-plugin-vanilla-extract");
+import { style } from '@vanilla-extract/css';
+export const $root = style({
+    zIndex: 1
+}, "$root");
+export const variants = {
+    "primary button": style({
+        zIndex: 2
+    }, "variants_primary button"),
+    2: style({
+        zIndex: 3
+    }, "variants_2")
+};
+__vanilla_filescope__.endFileScope();
@@ -0,0 +1,8 @@
+import { style } from '@vanilla-extract/css';
+
+export const $root = style({ zIndex: 1 });
+
+export const variants = {
+  "primary button": style({ zIndex: 2 }),
+  2: style({ zIndex: 3 }),
+};
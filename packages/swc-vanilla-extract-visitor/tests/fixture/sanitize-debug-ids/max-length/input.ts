@@ -0,0 +1,8 @@
+import { style } from '@vanilla-extract/css';
+
+export const navigation = {
+  primaryDropdownMenuItem: style({ zIndex: 1 }),
+  secondaryDropdownMenuItem: style({ zIndex: 2 }),
+};
+
+export const navigation_primaryDropdownMenuItem = style({ zIndex: 3 });
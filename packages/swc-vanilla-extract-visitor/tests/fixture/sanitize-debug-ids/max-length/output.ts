@@ -0,0 +1,15 @@
+/*@vanilla-extract/file-scope*/ import * as __vanilla_filescope__ from "@vanilla-extract/css/fileScope";
+__vanilla_filescope__.setFileScope("/cwd/dir/mockFilename.css.ts", "swc-plugin-vanilla-extract");
+import { style } from '@vanilla-extract/css';
+export const navigation = {
+    primaryDropdownMenuItem: style({
+        zIndex: 1
+    }, "navigation_primar_5bb97b"),
+    secondaryDropdownMenuItem: style({
+        zIndex: 2
+    }, "navigation_second_50dadf")
+};
+export const navigation_primaryDropdownMenuItem = style({
+    zIndex: 3
+}, "navigation_primar_2201ba");
+__vanilla_filescope__.endFileScope();
@@ -0,0 +1,13 @@
+import { style } from '@vanilla-extract/css';
+
+const card = (rule, id) => style(rule, id);
+
+export const root = card({ zIndex: 1 });
+
+export const themed = (card) => card({ zIndex: 2 });
+
+export function layout(rule) {
+  const card = (value, label) => [value, label];
+
+  return card(rule);
+}
@@ -0,0 +1,18 @@
+/*@vanilla-extract/file-scope*/ import * as __vanilla_filescope__ from "@vanilla-extract/css/fileScope";
+__vanilla_filescope__.setFileScope("/cwd/dir/mockFilename.css.ts", "swc-plugin-vanilla-extract");
+import { style } from '@vanilla-extract/css';
+const card = (rule, id)=>style(rule, id);
+export const root = card({
+    zIndex: 1
+}, "root");
+export const themed = (card)=>card({
+        zIndex: 2
+    });
+export function layout(rule) {
+    const card = (value, label)=>[
+            value,
+            label
+        ];
+    return card(rule);
+}
+__vanilla_filescope__.endFileScope();
@@ -0,0 +1,5 @@
+import { style } from '@vanilla-extract/css';
+
+export default style({
+    zIndex: 2,
+});
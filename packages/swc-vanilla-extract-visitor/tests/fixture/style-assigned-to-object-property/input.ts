@@ -0,0 +1,9 @@
+import { style } from '@vanilla-extract/css';
+
+const test = {
+  one: {
+    two: style({
+      zIndex: 2,
+    })
+  }
+};
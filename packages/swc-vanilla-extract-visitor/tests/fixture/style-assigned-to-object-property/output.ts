@@ -0,0 +1,11 @@
+/*@vanilla-extract/file-scope*/ import * as __vanilla_filescope__ from "@vanilla-extract/css/fileScope";
+__vanilla_filescope__.setFileScope("/cwd/dir/mockFilename.css.ts", "swc-plugin-vanilla-extract");
+import { style } from '@vanilla-extract/css';
+const test = {
+    one: {
+        two: style({
+            zIndex: 2
+        }, "test_one_two")
+    }
+};
+__vanilla_filescope__.endFileScope();
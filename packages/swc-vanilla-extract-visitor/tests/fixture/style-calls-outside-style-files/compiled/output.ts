@@ -0,0 +1,7 @@
+import { setFileScope, endFileScope } from '@vanilla-extract/css/fileScope';
+setFileScope('src/button.css.ts', 'some-package');
+import { style } from '@vanilla-extract/css';
+export const root = style({
+    zIndex: 1
+}, 'root');
+endFileScope();
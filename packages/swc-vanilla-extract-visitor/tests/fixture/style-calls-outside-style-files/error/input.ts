@@ -0,0 +1,8 @@
+import { style } from '@vanilla-extract/css';
+import { recipe } from '@vanilla-extract/recipes';
+
+export const root = style({ zIndex: 1 });
+
+export function createButton() {
+  return recipe({ base: root });
+}
@@ -0,0 +1,9 @@
+import { style, type StyleRule } from '@vanilla-extract/css';
+
+const base: StyleRule = { padding: 8 };
+
+export const card = (rule: StyleRule, debugId?: string) => style([base, rule], debugId);
+
+export default function createPanel(rule: StyleRule) {
+  return card(rule);
+}
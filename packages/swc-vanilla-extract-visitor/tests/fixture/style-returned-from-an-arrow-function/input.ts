@@ -0,0 +1,7 @@
+import { style } from '@vanilla-extract/css';
+
+const test = () => {
+  return style({
+    color: 'red'
+  });
+};
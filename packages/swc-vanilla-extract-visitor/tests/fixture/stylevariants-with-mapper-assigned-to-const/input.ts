@@ -0,0 +1,5 @@
+import { styleVariants } from '@vanilla-extract/css';
+
+const colors = styleVariants({
+  red: 'red'
+}, (color) => ({ color }));
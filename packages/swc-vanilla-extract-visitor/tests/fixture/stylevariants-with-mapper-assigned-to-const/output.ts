@@ -0,0 +1,9 @@
+/*@vanilla-extract/file-scope*/ import * as __vanilla_filescope__ from "@vanilla-extract/css/fileScope";
+__vanilla_filescope__.setFileScope("/cwd/dir/mockFilename.css.ts", "swc-plugin-vanilla-extract");
+import { styleVariants } from '@vanilla-extract/css';
+const colors = styleVariants({
+    red: 'red'
+}, (color)=>({
+        color
+    }), "colors");
+__vanilla_filescope__.endFileScope();
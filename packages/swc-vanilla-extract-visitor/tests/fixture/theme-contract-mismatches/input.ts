@@ -0,0 +1,44 @@
+import { createThemeContract, createTheme, createGlobalTheme } from '@vanilla-extract/css';
+
+export const vars = createThemeContract({
+  color: {
+    brand: null,
+    text: null,
+  },
+  space: {
+    small: null,
+    large: null,
+  },
+});
+
+const space = {
+  small: '4px',
+  large: '16px',
+};
+
+export const lightTheme = createTheme(vars, {
+  color: {
+    brand: 'blue',
+    text: 'black',
+  },
+  space,
+});
+
+export const darkTheme = createTheme(vars, {
+  color: {
+    brand: 'navy',
+    txt: 'white',
+  },
+  space: {
+    small: '4px',
+  },
+});
+
+createGlobalTheme(':root', vars, {
+  color: {
+    brand: 'blue',
+    text: 'black',
+  },
+  space,
+  fontSize: '16px',
+});
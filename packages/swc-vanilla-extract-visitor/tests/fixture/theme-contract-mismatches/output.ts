@@ -0,0 +1,42 @@
+/*@vanilla-extract/file-scope*/ import * as __vanilla_filescope__ from "@vanilla-extract/css/fileScope";
+__vanilla_filescope__.setFileScope("/cwd/dir/mockFilename.css.ts", "swc-plugin-vanilla-extract");
+import { createThemeContract, createTheme, createGlobalTheme } from '@vanilla-extract/css';
+export const vars = createThemeContract({
+    color: {
+        brand: null,
+        text: null
+    },
+    space: {
+        small: null,
+        large: null
+    }
+});
+const space = {
+    small: '4px',
+    large: '16px'
+};
+export const lightTheme = createTheme(vars, {
+    color: {
+        brand: 'blue',
+        text: 'black'
+    },
+    space
+}, "lightTheme");
+export const darkTheme = createTheme(vars, {
+    color: {
+        brand: 'navy',
+        txt: 'white'
+    },
+    space: {
+        small: '4px'
+    }
+}, "darkTheme");
+createGlobalTheme(':root', vars, {
+    color: {
+        brand: 'blue',
+        text: 'black'
+    },
+    space,
+    fontSize: '16px'
+});
+__vanilla_filescope__.endFileScope();
@@ -0,0 +1,37 @@
+import { style, globalStyle, styleVariants, recipe } from '@vanilla-extract/css';
+
+export const root = style({
+  backgroundColour: 'red',
+  WebkitTapHighlightColor: 'transparent',
+  '--gap': '4px',
+  ':hover': {
+    colr: 'blue',
+  },
+  selectors: {
+    '&:focus': { outline: 'none' },
+  },
+  '@media screen and (min-width: 768px)': {
+    padding: 4,
+  },
+  '@media': {
+    'screen and (min-width: 1024px)': { paddig: 8 },
+  },
+});
+
+globalStyle('html', {
+  '@page': {},
+});
+
+export const sizes = styleVariants({
+  small: { fontSize: 12 },
+  large: { fontSise: 16 },
+});
+
+export const button = recipe({
+  base: { display: 'flex' },
+  variants: {
+    tone: {
+      dark: { colour: 'white' },
+    },
+  },
+});
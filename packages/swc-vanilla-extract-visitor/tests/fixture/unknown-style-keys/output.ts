@@ -0,0 +1,48 @@
+/*@vanilla-extract/file-scope*/ import * as __vanilla_filescope__ from "@vanilla-extract/css/fileScope";
+__vanilla_filescope__.setFileScope("/cwd/dir/mockFilename.css.ts", "swc-plugin-vanilla-extract");
+import { style, globalStyle, styleVariants, recipe } from '@vanilla-extract/css';
+export const root = style({
+    backgroundColour: 'red',
+    WebkitTapHighlightColor: 'transparent',
+    '--gap': '4px',
+    ':hover': {
+        colr: 'blue'
+    },
+    selectors: {
+        '&:focus': {
+            outline: 'none'
+        }
+    },
+    '@media screen and (min-width: 768px)': {
+        padding: 4
+    },
+    '@media': {
+        'screen and (min-width: 1024px)': {
+            paddig: 8
+        }
+    }
+}, "root");
+globalStyle('html', {
+    '@page': {}
+});
+export const sizes = styleVariants({
+    small: {
+        fontSize: 12
+    },
+    large: {
+        fontSise: 16
+    }
+}, "sizes");
+export const button = recipe({
+    base: {
+        display: 'flex'
+    },
+    variants: {
+        tone: {
+            dark: {
+                colour: 'white'
+            }
+        }
+    }
+}, "button");
+__vanilla_filescope__.endFileScope();
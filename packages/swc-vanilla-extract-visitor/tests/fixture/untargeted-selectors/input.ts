@@ -0,0 +1,13 @@
+import { style } from '@vanilla-extract/css';
+
+export const parent = style({});
+
+export const child = style({
+  selectors: {
+    '&:hover, .parent &': { color: 'red' },
+    [`${parent} > &`]: { color: 'blue' },
+    '&:focus, & .icon': { color: 'green' },
+    [`${parent} .child`]: { color: 'white' },
+    [`${ parent }:hover ${child}`]: { color: 'black' },
+  },
+});
@@ -0,0 +1,24 @@
+/*@vanilla-extract/file-scope*/ import * as __vanilla_filescope__ from "@vanilla-extract/css/fileScope";
+__vanilla_filescope__.setFileScope("/cwd/dir/mockFilename.css.ts", "swc-plugin-vanilla-extract");
+import { style } from '@vanilla-extract/css';
+export const parent = style({}, "parent");
+export const child = style({
+    selectors: {
+        '&:hover, .parent &': {
+            color: 'red'
+        },
+        [`${parent} > &`]: {
+            color: 'blue'
+        },
+        '&:focus, & .icon': {
+            color: 'green'
+        },
+        [`${parent} .child`]: {
+            color: 'white'
+        },
+        [`${parent}:hover ${child}`]: {
+            color: 'black'
+        }
+    }
+}, "child");
+__vanilla_filescope__.endFileScope();
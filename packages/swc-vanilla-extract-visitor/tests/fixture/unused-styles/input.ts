@@ -0,0 +1,26 @@
+import { style, styleVariants, keyframes, createVar, globalStyle } from '@vanilla-extract/css';
+
+const spin = keyframes({
+  from: { transform: 'rotate(0deg)' },
+  to: { transform: 'rotate(360deg)' },
+});
+
+const base = style({
+  animation: `${spin} 1s linear infinite`,
+});
+
+const unusedBase = style({
+  display: 'flex',
+});
+
+const sizes = styleVariants({
+  small: { fontSize: 12 },
+});
+
+const color = createVar();
+
+export const root = style([base, { vars: { [color]: 'red' } }]);
+
+globalStyle('html', {
+  margin: 0,
+});
@@ -0,0 +1,35 @@
+/*@vanilla-extract/file-scope*/ import * as __vanilla_filescope__ from "@vanilla-extract/css/fileScope";
+__vanilla_filescope__.setFileScope("/cwd/dir/mockFilename.css.ts", "swc-plugin-vanilla-extract");
+import { style, styleVariants, keyframes, createVar, globalStyle } from '@vanilla-extract/css';
+const spin = keyframes({
+    from: {
+        transform: 'rotate(0deg)'
+    },
+    to: {
+        transform: 'rotate(360deg)'
+    }
+}, "spin");
+const base = style({
+    animation: `${spin} 1s linear infinite`
+}, "base");
+const unusedBase = style({
+    display: 'flex'
+}, "unusedBase");
+const sizes = styleVariants({
+    small: {
+        fontSize: 12
+    }
+}, "sizes");
+const color = createVar("color");
+export const root = style([
+    base,
+    {
+        vars: {
+            [color]: 'red'
+        }
+    }
+], "root");
+globalStyle('html', {
+    margin: 0
+});
+__vanilla_filescope__.endFileScope();
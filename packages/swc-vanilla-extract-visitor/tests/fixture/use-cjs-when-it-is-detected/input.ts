@@ -0,0 +1,5 @@
+const { style } = require('@vanilla-extract/css');
+
+const three = style({
+  zIndex: 2,
+});
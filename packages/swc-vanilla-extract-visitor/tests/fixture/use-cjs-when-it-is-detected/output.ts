@@ -0,0 +1,7 @@
+/*@vanilla-extract/file-scope*/ import * as __vanilla_filescope__ from "@vanilla-extract/css/fileScope";
+__vanilla_filescope__.setFileScope("/cwd/dir/mockFilename.css.ts", "swc-plugin-vanilla-extract");
+const { style  } = require('@vanilla-extract/css');
+const three = style({
+    zIndex: 2
+});
+__vanilla_filescope__.endFileScope();
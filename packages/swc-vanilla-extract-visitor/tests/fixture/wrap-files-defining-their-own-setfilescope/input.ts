@@ -0,0 +1,10 @@
+import { style } from '@vanilla-extract/css';
+
+function setFileScope(filePath: string) {
+  console.log(filePath);
+}
+setFileScope('src/dir/someFileName.css.ts');
+
+const three = style({
+  zIndex: 2,
+});
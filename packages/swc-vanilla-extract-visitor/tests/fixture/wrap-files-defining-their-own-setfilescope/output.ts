@@ -0,0 +1,11 @@
+/*@vanilla-extract/file-scope*/ import * as __vanilla_filescope__ from "@vanilla-extract/css/fileScope";
+__vanilla_filescope__.setFileScope("/cwd/dir/mockFilename.css.ts", "swc-plugin-vanilla-extract");
+import { style } from '@vanilla-extract/css';
+function setFileScope(filePath: string) {
+    console.log(filePath);
+}
+setFileScope('src/dir/someFileName.css.ts');
+const three = style({
+    zIndex: 2
+}, "three");
+__vanilla_filescope__.endFileScope();
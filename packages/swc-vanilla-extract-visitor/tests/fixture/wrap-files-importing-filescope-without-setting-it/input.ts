@@ -0,0 +1,8 @@
+import { getFileScope } from '@vanilla-extract/css/fileScope';
+import { style } from '@vanilla-extract/css';
+
+export const scope = getFileScope();
+
+const three = style({
+  zIndex: 2,
+});
@@ -0,0 +1,9 @@
+/*@vanilla-extract/file-scope*/ import * as __vanilla_filescope__ from "@vanilla-extract/css/fileScope";
+__vanilla_filescope__.setFileScope("/cwd/dir/mockFilename.css.ts", "swc-plugin-vanilla-extract");
+import { getFileScope } from '@vanilla-extract/css/fileScope';
+import { style } from '@vanilla-extract/css';
+export const scope = getFileScope();
+const three = style({
+    zIndex: 2
+}, "three");
+__vanilla_filescope__.endFileScope();
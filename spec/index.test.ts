@@ -10,10 +10,9 @@ const transform = (
   code: string,
   module?: "commonjs" | "es6",
   transformOptions?: Options,
-  filename?: string
+  filename?: string,
+  pluginOptions: Record<string, unknown> = {}
 ) => {
-  const pluginOptions = {};
-
   const options: Options = {
     filename: filename ?? "dir/mockFilename.css.ts",
     jsc: {
@@ -73,7 +72,7 @@ describe("babel plugin", () => {
     `;
 
     expect(transform(source)).toMatchInlineSnapshot(`
-      "import * as __vanilla_filescope__ from "@vanilla-extract/css/fileScope";
+      "/*@vanilla-extract/file-scope*/ import * as __vanilla_filescope__ from "@vanilla-extract/css/fileScope";
       __vanilla_filescope__.setFileScope("${cwd}/dir/mockFilename.css.ts", "swc-plugin-vanilla-extract");
       import { style } from '@vanilla-extract/css';
       const one = style({
@@ -94,7 +93,7 @@ describe("babel plugin", () => {
     `;
 
     expect(transform(source)).toMatchInlineSnapshot(`
-      "import * as __vanilla_filescope__ from "@vanilla-extract/css/fileScope";
+      "/*@vanilla-extract/file-scope*/ import * as __vanilla_filescope__ from "@vanilla-extract/css/fileScope";
       __vanilla_filescope__.setFileScope("${cwd}/dir/mockFilename.css.ts", "swc-plugin-vanilla-extract");
       import { styleVariants } from '@vanilla-extract/css';
       const colors = styleVariants({
@@ -117,7 +116,7 @@ describe("babel plugin", () => {
     `;
 
     expect(transform(source)).toMatchInlineSnapshot(`
-      "import * as __vanilla_filescope__ from "@vanilla-extract/css/fileScope";
+      "/*@vanilla-extract/file-scope*/ import * as __vanilla_filescope__ from "@vanilla-extract/css/fileScope";
       __vanilla_filescope__.setFileScope("${cwd}/dir/mockFilename.css.ts", "swc-plugin-vanilla-extract");
       import { styleVariants } from '@vanilla-extract/css';
       const colors = styleVariants({
@@ -140,12 +139,12 @@ describe("babel plugin", () => {
     `;
 
     expect(transform(source)).toMatchInlineSnapshot(`
-      "import * as __vanilla_filescope__ from "@vanilla-extract/css/fileScope";
+      "/*@vanilla-extract/file-scope*/ import * as __vanilla_filescope__ from "@vanilla-extract/css/fileScope";
       __vanilla_filescope__.setFileScope("${cwd}/dir/mockFilename.css.ts", "swc-plugin-vanilla-extract");
       import { style } from '@vanilla-extract/css';
       export default style({
           zIndex: 2
-      }, "default");
+      }, "mockFilename");
       __vanilla_filescope__.endFileScope();
       "
     `);
@@ -165,7 +164,7 @@ describe("babel plugin", () => {
     `;
 
     expect(transform(source)).toMatchInlineSnapshot(`
-      "import * as __vanilla_filescope__ from "@vanilla-extract/css/fileScope";
+      "/*@vanilla-extract/file-scope*/ import * as __vanilla_filescope__ from "@vanilla-extract/css/fileScope";
       __vanilla_filescope__.setFileScope("${cwd}/dir/mockFilename.css.ts", "swc-plugin-vanilla-extract");
       import { style } from '@vanilla-extract/css';
       const test = {
@@ -192,7 +191,7 @@ describe("babel plugin", () => {
     `;
 
     expect(transform(source)).toMatchInlineSnapshot(`
-      "import * as __vanilla_filescope__ from "@vanilla-extract/css/fileScope";
+      "/*@vanilla-extract/file-scope*/ import * as __vanilla_filescope__ from "@vanilla-extract/css/fileScope";
       __vanilla_filescope__.setFileScope("${cwd}/dir/mockFilename.css.ts", "swc-plugin-vanilla-extract");
       import { style } from '@vanilla-extract/css';
       const test = ()=>{
@@ -215,7 +214,7 @@ describe("babel plugin", () => {
     `;
 
     expect(transform(source)).toMatchInlineSnapshot(`
-      "import * as __vanilla_filescope__ from "@vanilla-extract/css/fileScope";
+      "/*@vanilla-extract/file-scope*/ import * as __vanilla_filescope__ from "@vanilla-extract/css/fileScope";
       __vanilla_filescope__.setFileScope("${cwd}/dir/mockFilename.css.ts", "swc-plugin-vanilla-extract");
       import { style } from '@vanilla-extract/css';
       const test = ()=>style({
@@ -238,7 +237,7 @@ describe("babel plugin", () => {
     `;
 
     expect(transform(source)).toMatchInlineSnapshot(`
-      "import * as __vanilla_filescope__ from "@vanilla-extract/css/fileScope";
+      "/*@vanilla-extract/file-scope*/ import * as __vanilla_filescope__ from "@vanilla-extract/css/fileScope";
       __vanilla_filescope__.setFileScope("${cwd}/dir/mockFilename.css.ts", "swc-plugin-vanilla-extract");
       import { style } from '@vanilla-extract/css';
       function test() {
@@ -259,7 +258,7 @@ describe("babel plugin", () => {
     `;
 
     expect(transform(source)).toMatchInlineSnapshot(`
-      "import * as __vanilla_filescope__ from "@vanilla-extract/css/fileScope";
+      "/*@vanilla-extract/file-scope*/ import * as __vanilla_filescope__ from "@vanilla-extract/css/fileScope";
       __vanilla_filescope__.setFileScope("${cwd}/dir/mockFilename.css.ts", "swc-plugin-vanilla-extract");
       import { globalStyle } from '@vanilla-extract/css';
       globalStyle('html, body', {
@@ -278,7 +277,7 @@ describe("babel plugin", () => {
     `;
 
     expect(transform(source)).toMatchInlineSnapshot(`
-      "import * as __vanilla_filescope__ from "@vanilla-extract/css/fileScope";
+      "/*@vanilla-extract/file-scope*/ import * as __vanilla_filescope__ from "@vanilla-extract/css/fileScope";
       __vanilla_filescope__.setFileScope("${cwd}/dir/mockFilename.css.ts", "swc-plugin-vanilla-extract");
       import { createVar } from '@vanilla-extract/css';
       const myVar = createVar("myVar");
@@ -295,7 +294,7 @@ describe("babel plugin", () => {
     `;
 
     expect(transform(source)).toMatchInlineSnapshot(`
-      "import * as __vanilla_filescope__ from "@vanilla-extract/css/fileScope";
+      "/*@vanilla-extract/file-scope*/ import * as __vanilla_filescope__ from "@vanilla-extract/css/fileScope";
       __vanilla_filescope__.setFileScope("${cwd}/dir/mockFilename.css.ts", "swc-plugin-vanilla-extract");
       import { createContainer } from '@vanilla-extract/css';
       const myContainer = createContainer("myContainer");
@@ -314,7 +313,7 @@ describe("babel plugin", () => {
     `;
 
     expect(transform(source)).toMatchInlineSnapshot(`
-      "import * as __vanilla_filescope__ from "@vanilla-extract/css/fileScope";
+      "/*@vanilla-extract/file-scope*/ import * as __vanilla_filescope__ from "@vanilla-extract/css/fileScope";
       __vanilla_filescope__.setFileScope("${cwd}/dir/mockFilename.css.ts", "swc-plugin-vanilla-extract");
       import { fontFace } from '@vanilla-extract/css';
       const myFont = fontFace({
@@ -335,7 +334,7 @@ describe("babel plugin", () => {
     `;
 
     expect(transform(source)).toMatchInlineSnapshot(`
-      "import * as __vanilla_filescope__ from "@vanilla-extract/css/fileScope";
+      "/*@vanilla-extract/file-scope*/ import * as __vanilla_filescope__ from "@vanilla-extract/css/fileScope";
       __vanilla_filescope__.setFileScope("${cwd}/dir/mockFilename.css.ts", "swc-plugin-vanilla-extract");
       import { globalFontFace } from '@vanilla-extract/css';
       globalFontFace('myFont', {
@@ -357,7 +356,7 @@ describe("babel plugin", () => {
     `;
 
     expect(transform(source)).toMatchInlineSnapshot(`
-      "import * as __vanilla_filescope__ from "@vanilla-extract/css/fileScope";
+      "/*@vanilla-extract/file-scope*/ import * as __vanilla_filescope__ from "@vanilla-extract/css/fileScope";
       __vanilla_filescope__.setFileScope("${cwd}/dir/mockFilename.css.ts", "swc-plugin-vanilla-extract");
       import { keyframes } from '@vanilla-extract/css';
       const myAnimation = keyframes({
@@ -384,7 +383,7 @@ describe("babel plugin", () => {
     `;
 
     expect(transform(source)).toMatchInlineSnapshot(`
-      "import * as __vanilla_filescope__ from "@vanilla-extract/css/fileScope";
+      "/*@vanilla-extract/file-scope*/ import * as __vanilla_filescope__ from "@vanilla-extract/css/fileScope";
       __vanilla_filescope__.setFileScope("${cwd}/dir/mockFilename.css.ts", "swc-plugin-vanilla-extract");
       import { globalKeyframes } from '@vanilla-extract/css';
       globalKeyframes('myKeyframes', {
@@ -408,7 +407,7 @@ describe("babel plugin", () => {
     `;
 
     expect(transform(source)).toMatchInlineSnapshot(`
-      "import * as __vanilla_filescope__ from "@vanilla-extract/css/fileScope";
+      "/*@vanilla-extract/file-scope*/ import * as __vanilla_filescope__ from "@vanilla-extract/css/fileScope";
       __vanilla_filescope__.setFileScope("${cwd}/dir/mockFilename.css.ts", "swc-plugin-vanilla-extract");
       import { createTheme } from '@vanilla-extract/css';
       const darkTheme = createTheme({}, {}, "darkTheme");
@@ -425,7 +424,7 @@ describe("babel plugin", () => {
     `;
 
     expect(transform(source)).toMatchInlineSnapshot(`
-      "import * as __vanilla_filescope__ from "@vanilla-extract/css/fileScope";
+      "/*@vanilla-extract/file-scope*/ import * as __vanilla_filescope__ from "@vanilla-extract/css/fileScope";
       __vanilla_filescope__.setFileScope("${cwd}/dir/mockFilename.css.ts", "swc-plugin-vanilla-extract");
       import { createTheme } from '@vanilla-extract/css';
       const [theme, vars] = createTheme({}, {}, "theme");
@@ -445,10 +444,48 @@ describe("babel plugin", () => {
     `;
 
     expect(transform(source)).toMatchInlineSnapshot(`
-      "import * as __vanilla_filescope__ from "@vanilla-extract/css/fileScope";
+      "/*@vanilla-extract/file-scope*/ import * as __vanilla_filescope__ from "@vanilla-extract/css/fileScope";
+      __vanilla_filescope__.setFileScope("${cwd}/dir/mockFilename.css.ts", "swc-plugin-vanilla-extract");
+      import { createTheme } from '@vanilla-extract/css';
+      var _createTheme = createTheme({}, "myThemeClass"), _createTheme2 = _slicedToArray(_createTheme, 2), myThemeClass = _createTheme2[0], vars = _createTheme2[1];
+      __vanilla_filescope__.endFileScope();
+      "
+    `);
+  });
+
+  it("should handle createTheme using destructuring when compiled with array helpers", () => {
+    const source = `
+      import { createTheme } from '@vanilla-extract/css';
+
+      var _createTheme = _sliced_to_array(createTheme({}, {}), 2), myThemeClass = _createTheme[0], vars = _createTheme[1];
+      var _a = __read(createTheme({}, {}), 2), otherThemeClass = _a[0], otherVars = _a[1];
+    `;
+
+    expect(transform(source)).toMatchInlineSnapshot(`
+      "/*@vanilla-extract/file-scope*/ import * as __vanilla_filescope__ from "@vanilla-extract/css/fileScope";
       __vanilla_filescope__.setFileScope("${cwd}/dir/mockFilename.css.ts", "swc-plugin-vanilla-extract");
       import { createTheme } from '@vanilla-extract/css';
-      var _createTheme = createTheme({}, "_createTheme"), _createTheme2 = _slicedToArray(_createTheme, 2), myThemeClass = _createTheme2[0], vars = _createTheme2[1];
+      var _createTheme = _sliced_to_array(createTheme({}, {}, "myThemeClass"), 2), myThemeClass = _createTheme[0], vars = _createTheme[1];
+      var _a = __read(createTheme({}, {}, "otherThemeClass"), 2), otherThemeClass = _a[0], otherVars = _a[1];
+      __vanilla_filescope__.endFileScope();
+      "
+    `);
+  });
+
+  it("should handle destructuring when compiled in loose mode", () => {
+    const source = `
+      import * as css from '@vanilla-extract/css';
+
+      var _css_createTheme = css.createTheme({}, {}), myThemeClass = _css_createTheme[0], vars = _css_createTheme[1];
+      var _css_styleVariants = css.styleVariants({}), primary = _css_styleVariants.primary;
+    `;
+
+    expect(transform(source)).toMatchInlineSnapshot(`
+      "/*@vanilla-extract/file-scope*/ import * as __vanilla_filescope__ from "@vanilla-extract/css/fileScope";
+      __vanilla_filescope__.setFileScope("${cwd}/dir/mockFilename.css.ts", "swc-plugin-vanilla-extract");
+      import * as css from '@vanilla-extract/css';
+      var _css_createTheme = css.createTheme({}, {}, "myThemeClass"), myThemeClass = _css_createTheme[0], vars = _css_createTheme[1];
+      var _css_styleVariants = css.styleVariants({}, "primary"), primary = _css_styleVariants.primary;
       __vanilla_filescope__.endFileScope();
       "
     `);
@@ -462,7 +499,7 @@ describe("babel plugin", () => {
     `;
 
     expect(transform(source)).toMatchInlineSnapshot(`
-      "import * as __vanilla_filescope__ from "@vanilla-extract/css/fileScope";
+      "/*@vanilla-extract/file-scope*/ import * as __vanilla_filescope__ from "@vanilla-extract/css/fileScope";
       __vanilla_filescope__.setFileScope("${cwd}/dir/mockFilename.css.ts", "swc-plugin-vanilla-extract");
       import { createGlobalTheme } from '@vanilla-extract/css';
       const vars = createGlobalTheme(':root', {
@@ -483,7 +520,7 @@ describe("babel plugin", () => {
     `;
 
     expect(transform(source)).toMatchInlineSnapshot(`
-      "import * as __vanilla_filescope__ from "@vanilla-extract/css/fileScope";
+      "/*@vanilla-extract/file-scope*/ import * as __vanilla_filescope__ from "@vanilla-extract/css/fileScope";
       __vanilla_filescope__.setFileScope("${cwd}/dir/mockFilename.css.ts", "swc-plugin-vanilla-extract");
       import { createThemeContract } from '@vanilla-extract/css';
       const vars = createThemeContract({
@@ -502,7 +539,7 @@ describe("babel plugin", () => {
     `;
 
     expect(transform(source)).toMatchInlineSnapshot(`
-      "import * as __vanilla_filescope__ from "@vanilla-extract/css/fileScope";
+      "/*@vanilla-extract/file-scope*/ import * as __vanilla_filescope__ from "@vanilla-extract/css/fileScope";
       __vanilla_filescope__.setFileScope("${cwd}/dir/mockFilename.css.ts", "swc-plugin-vanilla-extract");
       import { recipe } from '@vanilla-extract/recipes';
       const button = recipe({}, "button");
@@ -527,7 +564,7 @@ describe("babel plugin", () => {
     `;
 
     expect(transform(source)).toMatchInlineSnapshot(`
-      "import * as __vanilla_filescope__ from "@vanilla-extract/css/fileScope";
+      "/*@vanilla-extract/file-scope*/ import * as __vanilla_filescope__ from "@vanilla-extract/css/fileScope";
       __vanilla_filescope__.setFileScope("${cwd}/dir/mockFilename.css.ts", "swc-plugin-vanilla-extract");
       import { style, styleVariants } from '@vanilla-extract/css';
       const three = style({
@@ -555,7 +592,7 @@ describe("babel plugin", () => {
     `;
 
     expect(transform(source)).toMatchInlineSnapshot(`
-      "import * as __vanilla_filescope__ from "@vanilla-extract/css/fileScope";
+      "/*@vanilla-extract/file-scope*/ import * as __vanilla_filescope__ from "@vanilla-extract/css/fileScope";
       __vanilla_filescope__.setFileScope("${cwd}/dir/mockFilename.css.ts", "swc-plugin-vanilla-extract");
       import { style } from 'some-other-package';
       const three = style({
@@ -575,8 +612,11 @@ describe("babel plugin", () => {
       });
     `;
 
-    expect(transform(source, "es6", {}, "dir/mockFilename.ts"))
-      .toMatchInlineSnapshot(`
+    expect(
+      transform(source, "es6", {}, "dir/mockFilename.ts", {
+        styleCallsOutsideStyleFiles: "off",
+      })
+    ).toMatchInlineSnapshot(`
       "import { style } from '@vanilla-extract/css';
       const three = style({
           zIndex: 2
@@ -585,6 +625,209 @@ describe("babel plugin", () => {
     `);
   });
 
+  it("should report style calls outside of style files", () => {
+    const source = `
+      import { style } from '@vanilla-extract/css';
+
+      export const root = style({
+        zIndex: 2,
+      });
+    `;
+
+    expect(() => transform(source, "es6", {}, "dir/button.tsx")).toThrow(
+      "`style` is called outside of a style file"
+    );
+  });
+
+  it("should not report style calls in helpers outside of style files", () => {
+    const source = `
+      import { style } from '@vanilla-extract/css';
+
+      const base = { padding: 8 };
+
+      export const card = (rule, debugId) => style([base, rule], debugId);
+
+      export default function createPanel(rule) {
+        return card(rule);
+      }
+    `;
+
+    expect(transform(source, "es6", {}, "dir/card.tsx")).toMatchInlineSnapshot(`
+      "import { style } from '@vanilla-extract/css';
+      const base = {
+          padding: 8
+      };
+      export const card = (rule, debugId)=>style([
+              base,
+              rule
+          ], debugId);
+      export default function createPanel(rule) {
+          return card(rule);
+      }
+      "
+    `);
+  });
+
+  it("should fail the transform on invalid options", () => {
+    const source = `
+      import { style } from '@vanilla-extract/css';
+
+      export const root = style({ zIndex: 1 });
+    `;
+
+    // Runs against the wasm plugin and the native binding, which share the options handling
+    expect(() =>
+      transform(source, "es6", {}, "dir/mockFilename.css.ts", { hmr: "parcel" })
+    ).toThrow(
+      "Invalid swc-plugin-vanilla-extract options: Unsupported hmr runtime: parcel"
+    );
+  });
+
+  it("should report style calls in functions which are not called", () => {
+    const source = `
+      import { style } from '@vanilla-extract/css';
+
+      const card = (rule) => style(rule);
+
+      export const root = card({ zIndex: 1 });
+
+      const createLazyStyle = () => style({ zIndex: 2 });
+    `;
+
+    expect(() =>
+      transform(source, "es6", {}, "dir/mockFilename.css.ts", {
+        lazyStyleCalls: "error",
+      })
+    ).toThrow("`style` is called in a function which does not run");
+  });
+
+  it("should report exports which can't be serialised", () => {
+    const source = `
+      import { style } from '@vanilla-extract/css';
+
+      export const root = style({ zIndex: 1 });
+
+      export const createStyle = (zIndex) => style({ zIndex });
+    `;
+
+    expect(() =>
+      transform(source, "es6", {}, "dir/mockFilename.css.ts", {
+        nonSerializableExports: "error",
+      })
+    ).toThrow("`createStyle` exports a function, which vanilla-extract can't serialise");
+  });
+
+  it("should report unknown keys in style rules", () => {
+    const source = `
+      import { style } from '@vanilla-extract/css';
+
+      export const root = style({
+        backgroundColour: 'red',
+        '@media screen': { zIndex: 1 },
+      });
+    `;
+
+    expect(() =>
+      transform(source, "es6", {}, "dir/mockFilename.css.ts", {
+        unknownStyleKeys: "error",
+      })
+    ).toThrow("Unknown CSS property `backgroundColour`, did you mean `backgroundColor`?");
+  });
+
+  it("should report selectors which do not target the style", () => {
+    const source = `
+      import { style } from '@vanilla-extract/css';
+
+      export const parent = style({});
+
+      export const child = style({
+        selectors: {
+          [\`\${parent} .child\`]: { zIndex: 1 },
+        },
+      });
+    `;
+
+    expect(() => transform(source)).toThrow(
+      "Selector `${parent} .child` does not target `&`"
+    );
+  });
+
+  it("should report denied imports of style files", () => {
+    const source = `
+      import { style } from '@vanilla-extract/css';
+      import { get } from 'lodash';
+
+      export const root = style({ zIndex: get(theme, 'zIndex') });
+    `;
+
+    expect(() =>
+      transform(source, "es6", {}, "dir/mockFilename.css.ts", {
+        importDenyList: ["lodash"],
+        deniedImports: "error",
+      })
+    ).toThrow("`lodash` matches denied import `lodash`");
+  });
+
+  it("should report themes which do not match their contract", () => {
+    const source = `
+      import { createThemeContract, createTheme } from '@vanilla-extract/css';
+
+      export const vars = createThemeContract({ color: { brand: null, text: null } });
+
+      export const theme = createTheme(vars, { color: { brand: 'blue' } });
+    `;
+
+    expect(() => transform(source)).toThrow(
+      "Token `color.text` of contract `vars` is missing"
+    );
+  });
+
+  it("should report unused styles", () => {
+    const source = `
+      import { style } from '@vanilla-extract/css';
+
+      const base = style({ zIndex: 1 });
+
+      export const root = style({ zIndex: 2 });
+    `;
+
+    expect(() =>
+      transform(source, "es6", {}, "dir/mockFilename.css.ts", {
+        unusedStyles: "error",
+      })
+    ).toThrow("`base` is created by `style` but never used or exported");
+  });
+
+  it("should configure rule severities by code", () => {
+    const source = `
+      import { style } from '@vanilla-extract/css';
+
+      const base = style({ zIndex: 1 });
+
+      export const root = style({ zIndex: 2 });
+    `;
+
+    expect(() =>
+      transform(source, "es6", {}, "dir/mockFilename.css.ts", {
+        rules: { VE007: "error" },
+      })
+    ).toThrow("`base` is created by `style` but never used or exported");
+  });
+
+  it("should report warnings as errors in strict mode", () => {
+    const source = `
+      import { style } from '@vanilla-extract/css';
+
+      export const root = style({ backgroundColour: 'red' });
+    `;
+
+    expect(() =>
+      transform(source, "es6", {}, "dir/mockFilename.css.ts", {
+        strict: true,
+      })
+    ).toThrow("Unknown CSS property `backgroundColour`");
+  });
+
   it("should ignore files that already have filescope information", () => {
     const source = `
       import { setFileScope, endFileScope } from '@vanilla-extract/css/fileScope';
@@ -609,192 +852,400 @@ describe("babel plugin", () => {
     `);
   });
 
-  it("should use CJS when it is detected", () => {
+  it("should ignore files already transformed by this plugin", () => {
     const source = `
-      const { style } = require('@vanilla-extract/css');
+      /*@vanilla-extract/file-scope*/ import * as __vanilla_filescope__ from "@vanilla-extract/css/fileScope";
+      __vanilla_filescope__.setFileScope("src/dir/someFileName.css.ts", "some-package");
+      import { style } from '@vanilla-extract/css';
 
       const three = style({
         zIndex: 2,
-      });
+      }, "three");
+      __vanilla_filescope__.endFileScope();
     `;
 
     expect(transform(source)).toMatchInlineSnapshot(`
-      "import * as __vanilla_filescope__ from "@vanilla-extract/css/fileScope";
-      __vanilla_filescope__.setFileScope("${cwd}/dir/mockFilename.css.ts", "swc-plugin-vanilla-extract");
-      const { style  } = require('@vanilla-extract/css');
+      "/*@vanilla-extract/file-scope*/ import * as __vanilla_filescope__ from "@vanilla-extract/css/fileScope";
+      __vanilla_filescope__.setFileScope("src/dir/someFileName.css.ts", "some-package");
+      import { style } from '@vanilla-extract/css';
       const three = style({
           zIndex: 2
-      });
+      }, "three");
       __vanilla_filescope__.endFileScope();
       "
     `);
   });
 
-  it("should ignore CJS files that already have filescope information", () => {
+  it("should ignore files with the plugin marker comment", () => {
     const source = `
-      const { setFileScope, endFileScope } = require('@vanilla-extract/css/fileScope');
-      setFileScope('src/dir/someFileName.css.ts', 'some-package');
-      const { style } = require('@vanilla-extract/css');
+      /*@vanilla-extract/file-scope*/ const fileScope = loadFileScope();
+      import { style } from '@vanilla-extract/css';
 
       const three = style({
         zIndex: 2,
       });
-      endFileScope();
     `;
 
     expect(transform(source)).toMatchInlineSnapshot(`
-      "const { setFileScope , endFileScope  } = require('@vanilla-extract/css/fileScope');
-      setFileScope('src/dir/someFileName.css.ts', 'some-package');
-      const { style  } = require('@vanilla-extract/css');
+      "/*@vanilla-extract/file-scope*/ const fileScope = loadFileScope();
+      import { style } from '@vanilla-extract/css';
       const three = style({
           zIndex: 2
       });
-      endFileScope();
       "
     `);
   });
 
-  it("should handle renaming imports", () => {
+  it("should wrap files defining their own setFileScope", () => {
     const source = `
-      import { style as specialStyle } from '@vanilla-extract/css';
+      import { style } from '@vanilla-extract/css';
 
-      const four = specialStyle({
+      function setFileScope(filePath) {
+        console.log(filePath);
+      }
+      setFileScope('src/dir/someFileName.css.ts');
+
+      const three = style({
         zIndex: 2,
       });
     `;
 
     expect(transform(source)).toMatchInlineSnapshot(`
-      "import * as __vanilla_filescope__ from "@vanilla-extract/css/fileScope";
+      "/*@vanilla-extract/file-scope*/ import * as __vanilla_filescope__ from "@vanilla-extract/css/fileScope";
       __vanilla_filescope__.setFileScope("${cwd}/dir/mockFilename.css.ts", "swc-plugin-vanilla-extract");
-      import { style as specialStyle } from '@vanilla-extract/css';
-      const four = specialStyle({
+      import { style } from '@vanilla-extract/css';
+      function setFileScope(filePath) {
+          console.log(filePath);
+      }
+      setFileScope('src/dir/someFileName.css.ts');
+      const three = style({
           zIndex: 2
-      }, "four");
+      }, "three");
       __vanilla_filescope__.endFileScope();
       "
     `);
   });
 
-  it("should handle anonymous style in arrays", () => {
+  it("should ignore files that import filescope from an optimized dependency", () => {
     const source = `
-       import { style } from '@vanilla-extract/css';
+      import { setFileScope, endFileScope } from '/node_modules/.vite/deps/@vanilla-extract_css_fileScope.js?v=1a2b3c';
+      setFileScope('src/dir/someFileName.css.ts', 'some-package');
+      import { style } from '@vanilla-extract/css';
 
-       export const height = [
-        style({
-          zIndex: 2,
-        })
-      ];
+      const three = style({
+        zIndex: 2,
+      });
+      endFileScope();
     `;
 
     expect(transform(source)).toMatchInlineSnapshot(`
-      "import * as __vanilla_filescope__ from "@vanilla-extract/css/fileScope";
-      __vanilla_filescope__.setFileScope("${cwd}/dir/mockFilename.css.ts", "swc-plugin-vanilla-extract");
+      "import { setFileScope, endFileScope } from '/node_modules/.vite/deps/@vanilla-extract_css_fileScope.js?v=1a2b3c';
+      setFileScope('src/dir/someFileName.css.ts', 'some-package');
       import { style } from '@vanilla-extract/css';
-      export const height = [
-          style({
-              zIndex: 2
-          }, "height")
-      ];
-      __vanilla_filescope__.endFileScope();
+      const three = style({
+          zIndex: 2
+      });
+      endFileScope();
       "
     `);
   });
 
-  it("should handle object key with anonymous style in arrays", () => {
+  it("should ignore files compiled by esbuild", () => {
     const source = `
-       import { style } from '@vanilla-extract/css';
-
-       export const height = {
-        full: [style({
-          zIndex: 2,
-        })]
-       };
+      var button_css_exports = {};
+      __export(button_css_exports, {
+        root: () => root
+      });
+      module.exports = __toCommonJS(button_css_exports);
+      var import_fileScope = require("@vanilla-extract/css/fileScope");
+      var import_css = __toESM(require("@vanilla-extract/css"));
+      (0, import_fileScope.setFileScope)("src/button.css.ts", "my-app");
+      var root = (0, import_css.style)({ zIndex: 1 }, "root");
+      (0, import_fileScope.endFileScope)();
     `;
 
     expect(transform(source)).toMatchInlineSnapshot(`
-      "import * as __vanilla_filescope__ from "@vanilla-extract/css/fileScope";
-      __vanilla_filescope__.setFileScope("${cwd}/dir/mockFilename.css.ts", "swc-plugin-vanilla-extract");
-      import { style } from '@vanilla-extract/css';
-      export const height = {
-          full: [
-              style({
-                  zIndex: 2
-              }, "height_full")
-          ]
-      };
-      __vanilla_filescope__.endFileScope();
+      "var button_css_exports = {};
+      __export(button_css_exports, {
+          root: ()=>root
+      });
+      module.exports = __toCommonJS(button_css_exports);
+      var import_fileScope = require("@vanilla-extract/css/fileScope");
+      var import_css = __toESM(require("@vanilla-extract/css"));
+      (0, import_fileScope.setFileScope)("src/button.css.ts", "my-app");
+      var root = (0, import_css.style)({
+          zIndex: 1
+      }, "root");
+      (0, import_fileScope.endFileScope)();
       "
     `);
   });
 
-  it("should handle namespace imports", () => {
+  it("should ignore files compiled by vite", () => {
     const source = `
-      import * as css from '@vanilla-extract/css';
-
-      const one = css.style({
-          zIndex: 2,
-      });
+      const __vi_ssr_import_0__ = await __vi_ssr_import__("@vanilla-extract/css/fileScope");
+      __vi_ssr_import_0__.setFileScope("src/button.css.ts", "my-app");
+      const __vi_ssr_import_1__ = await __vi_ssr_import__("@vanilla-extract/css");
+      const root = __vi_ssr_import_1__.style({ zIndex: 1 }, "root");
+      Object.defineProperty(__vi_ssr_exports__, "root", { enumerable: true, configurable: true, get(){ return root }});
+      __vi_ssr_import_0__.endFileScope();
     `;
 
     expect(transform(source)).toMatchInlineSnapshot(`
-      "import * as __vanilla_filescope__ from "@vanilla-extract/css/fileScope";
-      __vanilla_filescope__.setFileScope("${cwd}/dir/mockFilename.css.ts", "swc-plugin-vanilla-extract");
-      import * as css from '@vanilla-extract/css';
-      const one = css.style({
-          zIndex: 2
-      }, "one");
-      __vanilla_filescope__.endFileScope();
+      "const __vi_ssr_import_0__ = await __vi_ssr_import__("@vanilla-extract/css/fileScope");
+      __vi_ssr_import_0__.setFileScope("src/button.css.ts", "my-app");
+      const __vi_ssr_import_1__ = await __vi_ssr_import__("@vanilla-extract/css");
+      const root = __vi_ssr_import_1__.style({
+          zIndex: 1
+      }, "root");
+      Object.defineProperty(__vi_ssr_exports__, "root", {
+          enumerable: true,
+          configurable: true,
+          get () {
+              return root;
+          }
+      });
+      __vi_ssr_import_0__.endFileScope();
       "
     `);
   });
 
-  it("should handle nested call expressions", () => {
+  it("should wrap files importing filescope without setting it", () => {
     const source = `
+      import { getFileScope } from '@vanilla-extract/css/fileScope';
       import { style } from '@vanilla-extract/css';
 
-      const one = instrument(style({
-        zIndex: 1,
-      }));
+      export const scope = getFileScope();
 
-      const two = instrument(instrument(style({
+      const three = style({
         zIndex: 2,
-      })));
-
-      const three = instrument(instrument(instrument(style({
-        zIndex: 3,
-      }))));
+      });
     `;
 
     expect(transform(source)).toMatchInlineSnapshot(`
-      "import * as __vanilla_filescope__ from "@vanilla-extract/css/fileScope";
+      "/*@vanilla-extract/file-scope*/ import * as __vanilla_filescope__ from "@vanilla-extract/css/fileScope";
       __vanilla_filescope__.setFileScope("${cwd}/dir/mockFilename.css.ts", "swc-plugin-vanilla-extract");
+      import { getFileScope } from '@vanilla-extract/css/fileScope';
       import { style } from '@vanilla-extract/css';
-      const one = instrument(style({
-          zIndex: 1
-      }, "one"));
-      const two = instrument(instrument(style({
+      export const scope = getFileScope();
+      const three = style({
           zIndex: 2
-      }, "two")));
-      const three = instrument(instrument(instrument(style({
-          zIndex: 3
-      }, "three"))));
+      }, "three");
       __vanilla_filescope__.endFileScope();
       "
     `);
   });
 
-  it("should handle instrumentation via sequence expresions", () => {
+  it("should ignore CJS files that require filescope through an interop helper", () => {
     const source = `
-      import { style } from '@vanilla-extract/css';
+      var _fileScope = _interop_require_wildcard(require('@vanilla-extract/css/fileScope'));
+      (0, _fileScope.setFileScope)('src/dir/someFileName.css.ts', 'some-package');
+      const { style } = require('@vanilla-extract/css');
 
-      const one = (something++, style({
-        zIndex: 1,
-      }));
+      const three = style({
+        zIndex: 2,
+      });
+      (0, _fileScope.endFileScope)();
     `;
 
     expect(transform(source)).toMatchInlineSnapshot(`
-      "import * as __vanilla_filescope__ from "@vanilla-extract/css/fileScope";
-      __vanilla_filescope__.setFileScope("${cwd}/dir/mockFilename.css.ts", "swc-plugin-vanilla-extract");
+      "var _fileScope = _interop_require_wildcard(require('@vanilla-extract/css/fileScope'));
+      (0, _fileScope.setFileScope)('src/dir/someFileName.css.ts', 'some-package');
+      const { style  } = require('@vanilla-extract/css');
+      const three = style({
+          zIndex: 2
+      });
+      (0, _fileScope.endFileScope)();
+      "
+    `);
+  });
+
+  it("should use CJS when it is detected", () => {
+    const source = `
+      const { style } = require('@vanilla-extract/css');
+
+      const three = style({
+        zIndex: 2,
+      });
+    `;
+
+    expect(transform(source)).toMatchInlineSnapshot(`
+      "/*@vanilla-extract/file-scope*/ import * as __vanilla_filescope__ from "@vanilla-extract/css/fileScope";
+      __vanilla_filescope__.setFileScope("${cwd}/dir/mockFilename.css.ts", "swc-plugin-vanilla-extract");
+      const { style  } = require('@vanilla-extract/css');
+      const three = style({
+          zIndex: 2
+      });
+      __vanilla_filescope__.endFileScope();
+      "
+    `);
+  });
+
+  it("should ignore CJS files that already have filescope information", () => {
+    const source = `
+      const { setFileScope, endFileScope } = require('@vanilla-extract/css/fileScope');
+      setFileScope('src/dir/someFileName.css.ts', 'some-package');
+      const { style } = require('@vanilla-extract/css');
+
+      const three = style({
+        zIndex: 2,
+      });
+      endFileScope();
+    `;
+
+    expect(transform(source)).toMatchInlineSnapshot(`
+      "const { setFileScope , endFileScope  } = require('@vanilla-extract/css/fileScope');
+      setFileScope('src/dir/someFileName.css.ts', 'some-package');
+      const { style  } = require('@vanilla-extract/css');
+      const three = style({
+          zIndex: 2
+      });
+      endFileScope();
+      "
+    `);
+  });
+
+  it("should handle renaming imports", () => {
+    const source = `
+      import { style as specialStyle } from '@vanilla-extract/css';
+
+      const four = specialStyle({
+        zIndex: 2,
+      });
+    `;
+
+    expect(transform(source)).toMatchInlineSnapshot(`
+      "/*@vanilla-extract/file-scope*/ import * as __vanilla_filescope__ from "@vanilla-extract/css/fileScope";
+      __vanilla_filescope__.setFileScope("${cwd}/dir/mockFilename.css.ts", "swc-plugin-vanilla-extract");
+      import { style as specialStyle } from '@vanilla-extract/css';
+      const four = specialStyle({
+          zIndex: 2
+      }, "four");
+      __vanilla_filescope__.endFileScope();
+      "
+    `);
+  });
+
+  it("should handle anonymous style in arrays", () => {
+    const source = `
+       import { style } from '@vanilla-extract/css';
+
+       export const height = [
+        style({
+          zIndex: 2,
+        })
+      ];
+    `;
+
+    expect(transform(source)).toMatchInlineSnapshot(`
+      "/*@vanilla-extract/file-scope*/ import * as __vanilla_filescope__ from "@vanilla-extract/css/fileScope";
+      __vanilla_filescope__.setFileScope("${cwd}/dir/mockFilename.css.ts", "swc-plugin-vanilla-extract");
+      import { style } from '@vanilla-extract/css';
+      export const height = [
+          style({
+              zIndex: 2
+          }, "height")
+      ];
+      __vanilla_filescope__.endFileScope();
+      "
+    `);
+  });
+
+  it("should handle object key with anonymous style in arrays", () => {
+    const source = `
+       import { style } from '@vanilla-extract/css';
+
+       export const height = {
+        full: [style({
+          zIndex: 2,
+        })]
+       };
+    `;
+
+    expect(transform(source)).toMatchInlineSnapshot(`
+      "/*@vanilla-extract/file-scope*/ import * as __vanilla_filescope__ from "@vanilla-extract/css/fileScope";
+      __vanilla_filescope__.setFileScope("${cwd}/dir/mockFilename.css.ts", "swc-plugin-vanilla-extract");
+      import { style } from '@vanilla-extract/css';
+      export const height = {
+          full: [
+              style({
+                  zIndex: 2
+              }, "height_full")
+          ]
+      };
+      __vanilla_filescope__.endFileScope();
+      "
+    `);
+  });
+
+  it("should handle namespace imports", () => {
+    const source = `
+      import * as css from '@vanilla-extract/css';
+
+      const one = css.style({
+          zIndex: 2,
+      });
+    `;
+
+    expect(transform(source)).toMatchInlineSnapshot(`
+      "/*@vanilla-extract/file-scope*/ import * as __vanilla_filescope__ from "@vanilla-extract/css/fileScope";
+      __vanilla_filescope__.setFileScope("${cwd}/dir/mockFilename.css.ts", "swc-plugin-vanilla-extract");
+      import * as css from '@vanilla-extract/css';
+      const one = css.style({
+          zIndex: 2
+      }, "one");
+      __vanilla_filescope__.endFileScope();
+      "
+    `);
+  });
+
+  it("should handle nested call expressions", () => {
+    const source = `
+      import { style } from '@vanilla-extract/css';
+
+      const one = instrument(style({
+        zIndex: 1,
+      }));
+
+      const two = instrument(instrument(style({
+        zIndex: 2,
+      })));
+
+      const three = instrument(instrument(instrument(style({
+        zIndex: 3,
+      }))));
+    `;
+
+    expect(transform(source)).toMatchInlineSnapshot(`
+      "/*@vanilla-extract/file-scope*/ import * as __vanilla_filescope__ from "@vanilla-extract/css/fileScope";
+      __vanilla_filescope__.setFileScope("${cwd}/dir/mockFilename.css.ts", "swc-plugin-vanilla-extract");
+      import { style } from '@vanilla-extract/css';
+      const one = instrument(style({
+          zIndex: 1
+      }, "one"));
+      const two = instrument(instrument(style({
+          zIndex: 2
+      }, "two")));
+      const three = instrument(instrument(instrument(style({
+          zIndex: 3
+      }, "three"))));
+      __vanilla_filescope__.endFileScope();
+      "
+    `);
+  });
+
+  it("should handle instrumentation via sequence expresions", () => {
+    const source = `
+      import { style } from '@vanilla-extract/css';
+
+      const one = (something++, style({
+        zIndex: 1,
+      }));
+    `;
+
+    expect(transform(source)).toMatchInlineSnapshot(`
+      "/*@vanilla-extract/file-scope*/ import * as __vanilla_filescope__ from "@vanilla-extract/css/fileScope";
+      __vanilla_filescope__.setFileScope("${cwd}/dir/mockFilename.css.ts", "swc-plugin-vanilla-extract");
       import { style } from '@vanilla-extract/css';
       const one = (something++, style({
           zIndex: 1
@@ -803,4 +1254,703 @@ describe("babel plugin", () => {
       "
     `);
   });
+
+  it("should use package name from package map", () => {
+    const source = `
+      import { style } from '@vanilla-extract/css';
+
+      const one = style({
+          zIndex: 2,
+      });
+    `;
+
+    const pluginOptions = {
+      packageName: "@acme/root",
+      packageMap: {
+        "packages/**": "@acme/packages",
+        "packages/button/**": "@acme/button",
+      },
+    };
+
+    expect(
+      transform(
+        source,
+        "es6",
+        {},
+        "packages/button/src/button.css.ts",
+        pluginOptions
+      )
+    ).toMatchInlineSnapshot(`
+      "/*@vanilla-extract/file-scope*/ import * as __vanilla_filescope__ from "@vanilla-extract/css/fileScope";
+      __vanilla_filescope__.setFileScope("${cwd}/packages/button/src/button.css.ts", "@acme/button");
+      import { style } from '@vanilla-extract/css';
+      const one = style({
+          zIndex: 2
+      }, "one");
+      __vanilla_filescope__.endFileScope();
+      "
+    `);
+
+    expect(
+      transform(source, "es6", {}, "dir/mockFilename.css.ts", pluginOptions)
+    ).toMatchInlineSnapshot(`
+      "/*@vanilla-extract/file-scope*/ import * as __vanilla_filescope__ from "@vanilla-extract/css/fileScope";
+      __vanilla_filescope__.setFileScope("${cwd}/dir/mockFilename.css.ts", "@acme/root");
+      import { style } from '@vanilla-extract/css';
+      const one = style({
+          zIndex: 2
+      }, "one");
+      __vanilla_filescope__.endFileScope();
+      "
+    `);
+  });
+
+  it("should handle multiple styles in a single declaration", () => {
+    const source = `
+      import { style } from '@vanilla-extract/css';
+
+      const test = {
+        one: style({ zIndex: 1 }),
+        two: style({ zIndex: 2 }),
+      };
+    `;
+
+    expect(transform(source)).toMatchInlineSnapshot(`
+      "/*@vanilla-extract/file-scope*/ import * as __vanilla_filescope__ from "@vanilla-extract/css/fileScope";
+      __vanilla_filescope__.setFileScope("${cwd}/dir/mockFilename.css.ts", "swc-plugin-vanilla-extract");
+      import { style } from '@vanilla-extract/css';
+      const test = {
+          one: style({
+              zIndex: 1
+          }, "test_one"),
+          two: style({
+              zIndex: 2
+          }, "test_two")
+      };
+      __vanilla_filescope__.endFileScope();
+      "
+    `);
+  });
+
+  it("should handle debug id directives", () => {
+    const source = `
+      import { style } from '@vanilla-extract/css';
+
+      const test = {
+        one: /* @ve-ignore */ style({ zIndex: 1 }),
+        two: style({ zIndex: 2 }),
+      };
+
+      /* @ve-debug-id primaryButton */
+      export default style({ zIndex: 3 });
+
+      const list = [/* @ve-ignore */ style({ zIndex: 4 })];
+    `;
+
+    expect(transform(source)).toMatchInlineSnapshot(`
+      "/*@vanilla-extract/file-scope*/ import * as __vanilla_filescope__ from "@vanilla-extract/css/fileScope";
+      __vanilla_filescope__.setFileScope("${cwd}/dir/mockFilename.css.ts", "swc-plugin-vanilla-extract");
+      import { style } from '@vanilla-extract/css';
+      const test = {
+          one: /* @ve-ignore */ style({
+              zIndex: 1
+          }),
+          two: style({
+              zIndex: 2
+          }, "test_two")
+      };
+      /* @ve-debug-id primaryButton */ export default style({
+          zIndex: 3
+      }, "primaryButton");
+      const list = [
+          /* @ve-ignore */ style({
+              zIndex: 4
+          })
+      ];
+      __vanilla_filescope__.endFileScope();
+      "
+    `);
+  });
+
+  it("should not apply directives to nested calls", () => {
+    const source = `
+      import { style, styleVariants } from '@vanilla-extract/css';
+
+      /* @ve-debug-id primaryButton */
+      export const button = [style({ zIndex: 1 }), style({ zIndex: 2 })];
+
+      // @ve-ignore
+      export const variants = styleVariants({
+        primary: style({ zIndex: 3 }),
+        secondary: style({ zIndex: 4 }),
+      });
+    `;
+
+    expect(transform(source)).toMatchInlineSnapshot(`
+      "/*@vanilla-extract/file-scope*/ import * as __vanilla_filescope__ from "@vanilla-extract/css/fileScope";
+      __vanilla_filescope__.setFileScope("${cwd}/dir/mockFilename.css.ts", "swc-plugin-vanilla-extract");
+      import { style, styleVariants } from '@vanilla-extract/css';
+      /* @ve-debug-id primaryButton */ export const button = [
+          style({
+              zIndex: 1
+          }, "button"),
+          style({
+              zIndex: 2
+          }, "button_2")
+      ];
+      // @ve-ignore
+      export const variants = styleVariants({
+          primary: style({
+              zIndex: 3
+          }, "variants_primary"),
+          secondary: style({
+              zIndex: 4
+          }, "variants_secondary")
+      });
+      __vanilla_filescope__.endFileScope();
+      "
+    `);
+  });
+
+  it("should handle file level directives", () => {
+    const source = `
+      // @ve-disable-file-scope
+      import { style } from '@vanilla-extract/css';
+
+      const one = style({ zIndex: 1 });
+    `;
+
+    expect(transform(source)).toMatchInlineSnapshot(`
+      "// @ve-disable-file-scope
+      import { style } from '@vanilla-extract/css';
+      const one = style({
+          zIndex: 1
+      }, "one");
+      "
+    `);
+
+    const disableDebugIdSource = `
+      /* @ve-disable-debug-id */
+      import { style } from '@vanilla-extract/css';
+
+      const one = style({ zIndex: 1 });
+    `;
+
+    expect(transform(disableDebugIdSource)).toMatchInlineSnapshot(`
+      "/* @ve-disable-debug-id */ /*@vanilla-extract/file-scope*/ import * as __vanilla_filescope__ from "@vanilla-extract/css/fileScope";
+      __vanilla_filescope__.setFileScope("${cwd}/dir/mockFilename.css.ts", "swc-plugin-vanilla-extract");
+      import { style } from '@vanilla-extract/css';
+      const one = style({
+          zIndex: 1
+      });
+      __vanilla_filescope__.endFileScope();
+      "
+    `);
+  });
+
+  it("should expand debug id template", () => {
+    const source = `
+      import { style } from '@vanilla-extract/css';
+
+      export const root = style({ zIndex: 1 });
+
+      export default style({ zIndex: 2 });
+    `;
+
+    expect(
+      transform(source, "es6", {}, "dir/button.css.ts", {
+        debugIdTemplate: "[fileStem]_[name]_L[line]",
+      })
+    ).toMatchInlineSnapshot(`
+      "/*@vanilla-extract/file-scope*/ import * as __vanilla_filescope__ from "@vanilla-extract/css/fileScope";
+      __vanilla_filescope__.setFileScope("${cwd}/dir/button.css.ts", "swc-plugin-vanilla-extract");
+      import { style } from '@vanilla-extract/css';
+      export const root = style({
+          zIndex: 1
+      }, "button_root_L4");
+      export default style({
+          zIndex: 2
+      }, "button_button_L6");
+      __vanilla_filescope__.endFileScope();
+      "
+    `);
+  });
+
+  it("should sanitize debug ids", () => {
+    const source = `
+      import { style } from '@vanilla-extract/css';
+
+      export const $root = style({ zIndex: 1 });
+
+      export const variants = {
+        "primary button": style({ zIndex: 2 }),
+        2: style({ zIndex: 3 }),
+      };
+    `;
+
+    expect(
+      transform(source, "es6", {}, "dir/mockFilename.css.ts", {
+        debugIdSanitize: true,
+      })
+    ).toMatchInlineSnapshot(`
+      "/*@vanilla-extract/file-scope*/ import * as __vanilla_filescope__ from "@vanilla-extract/css/fileScope";
+      __vanilla_filescope__.setFileScope("${cwd}/dir/mockFilename.css.ts", "swc-plugin-vanilla-extract");
+      import { style } from '@vanilla-extract/css';
+      export const $root = style({
+          zIndex: 1
+      }, "_root");
+      export const variants = {
+          "primary button": style({
+              zIndex: 2
+          }, "variants_primary_button"),
+          2: style({
+              zIndex: 3
+          }, "variants_2")
+      };
+      __vanilla_filescope__.endFileScope();
+      "
+    `);
+
+    expect(transform(source, "es6", {}, "dir/mockFilename.css.ts"))
+      .toMatchInlineSnapshot(`
+      "/*@vanilla-extract/file-scope*/ import * as __vanilla_filescope__ from "@vanilla-extract/css/fileScope";
+      __vanilla_filescope__.setFileScope("${cwd}/dir/mockFilename.css.ts", "swc-plugin-vanilla-extract");
+      import { style } from '@vanilla-extract/css';
+      export const $root = style({
+          zIndex: 1
+      }, "$root");
+      export const variants = {
+          "primary button": style({
+              zIndex: 2
+          }, "variants_primary button"),
+          2: style({
+              zIndex: 3
+          }, "variants_2")
+      };
+      __vanilla_filescope__.endFileScope();
+      "
+    `);
+  });
+
+  it("should disambiguate duplicate debug ids", () => {
+    const source = `
+      import { style } from '@vanilla-extract/css';
+
+      export const button = {
+        primary_root: style({ zIndex: 1 }),
+        primary: { root: style({ zIndex: 2 }) },
+      };
+
+      /* @ve-debug-id button_primary_root */
+      export const other = style({ zIndex: 3 });
+    `;
+
+    expect(transform(source, "es6", {}, "dir/mockFilename.css.ts"))
+      .toMatchInlineSnapshot(`
+      "/*@vanilla-extract/file-scope*/ import * as __vanilla_filescope__ from "@vanilla-extract/css/fileScope";
+      __vanilla_filescope__.setFileScope("${cwd}/dir/mockFilename.css.ts", "swc-plugin-vanilla-extract");
+      import { style } from '@vanilla-extract/css';
+      export const button = {
+          primary_root: style({
+              zIndex: 1
+          }, "button_primary_root"),
+          primary: {
+              root: style({
+                  zIndex: 2
+              }, "button_primary_root_2")
+          }
+      };
+      /* @ve-debug-id button_primary_root */ export const other = style({
+          zIndex: 3
+      }, "button_primary_root_3");
+      __vanilla_filescope__.endFileScope();
+      "
+    `);
+  });
+
+  it("should inject debug ids into local wrapper functions", () => {
+    const source = `
+      import { style } from '@vanilla-extract/css';
+
+      const card = (rule, id) => style(rule, id);
+
+      function panel(rule, options, debugId) {
+        return card({ ...rule, ...options }, debugId);
+      }
+
+      export const root = card({ zIndex: 1 });
+
+      export const explicit = card({ zIndex: 2 }, 'explicit');
+
+      export const box = panel({ zIndex: 3 });
+    `;
+
+    expect(transform(source, "es6", {}, "dir/mockFilename.css.ts"))
+      .toMatchInlineSnapshot(`
+      "/*@vanilla-extract/file-scope*/ import * as __vanilla_filescope__ from "@vanilla-extract/css/fileScope";
+      __vanilla_filescope__.setFileScope("${cwd}/dir/mockFilename.css.ts", "swc-plugin-vanilla-extract");
+      import { style } from '@vanilla-extract/css';
+      const card = (rule, id)=>style(rule, id);
+      function panel(rule, options, debugId) {
+          return card({
+              ...rule,
+              ...options
+          }, debugId);
+      }
+      export const root = card({
+          zIndex: 1
+      }, "root");
+      export const explicit = card({
+          zIndex: 2
+      }, 'explicit');
+      export const box = panel({
+          zIndex: 3
+      }, undefined, "box");
+      __vanilla_filescope__.endFileScope();
+      "
+    `);
+  });
+
+  it("should skip debug ids of shadowed wrapper functions", () => {
+    const source = `
+      import { style } from '@vanilla-extract/css';
+
+      const card = (rule, id) => style(rule, id);
+
+      export const root = card({ zIndex: 1 });
+
+      export const themed = (card) => card({ zIndex: 2 });
+
+      export function layout(rule) {
+        const card = (value, label) => [value, label];
+
+        return card(rule);
+      }
+    `;
+
+    expect(transform(source)).toMatchInlineSnapshot(`
+      "/*@vanilla-extract/file-scope*/ import * as __vanilla_filescope__ from "@vanilla-extract/css/fileScope";
+      __vanilla_filescope__.setFileScope("${cwd}/dir/mockFilename.css.ts", "swc-plugin-vanilla-extract");
+      import { style } from '@vanilla-extract/css';
+      const card = (rule, id)=>style(rule, id);
+      export const root = card({
+          zIndex: 1
+      }, "root");
+      export const themed = (card)=>card({
+              zIndex: 2
+          });
+      export function layout(rule) {
+          const card = (value, label)=>[
+                  value,
+                  label
+              ];
+          return card(rule);
+      }
+      __vanilla_filescope__.endFileScope();
+      "
+    `);
+  });
+
+  it("should handle hot updates in hmr mode", () => {
+    const source = `
+      import { style } from '@vanilla-extract/css';
+
+      export const root = style({ zIndex: 1 });
+    `;
+
+    expect(
+      transform(source, "es6", {}, "dir/mockFilename.css.ts", {
+        hmr: "vite",
+      })
+    ).toMatchInlineSnapshot(`
+      "/*@vanilla-extract/file-scope*/ import * as __vanilla_filescope__ from "@vanilla-extract/css/fileScope";
+      __vanilla_filescope__.setFileScope("${cwd}/dir/mockFilename.css.ts", "swc-plugin-vanilla-extract");
+      import { style } from '@vanilla-extract/css';
+      export const root = style({
+          zIndex: 1
+      }, "root");
+      __vanilla_filescope__.endFileScope();
+      if (import.meta.hot) {
+          import.meta.hot.dispose(()=>{
+              if (typeof document !== 'undefined') {
+                  document.querySelectorAll('style[data-file="${cwd}/dir/mockFilename.css.ts"]').forEach((style)=>{
+                      style.textContent = '';
+                  });
+              }
+          });
+      }
+      "
+    `);
+  });
+
+  it("should accept hot updates in hmr mode when hmrAccept is set", () => {
+    const source = `
+      import { style } from '@vanilla-extract/css';
+
+      export const root = style({ zIndex: 1 });
+    `;
+
+    expect(
+      transform(source, "es6", {}, "dir/mockFilename.css.ts", {
+        hmr: "vite",
+        hmrAccept: true,
+      })
+    ).toMatchInlineSnapshot(`
+      "/*@vanilla-extract/file-scope*/ import * as __vanilla_filescope__ from "@vanilla-extract/css/fileScope";
+      __vanilla_filescope__.setFileScope("${cwd}/dir/mockFilename.css.ts", "swc-plugin-vanilla-extract");
+      import { style } from '@vanilla-extract/css';
+      export const root = style({
+          zIndex: 1
+      }, "root");
+      __vanilla_filescope__.endFileScope();
+      if (import.meta.hot) {
+          import.meta.hot.accept();
+          import.meta.hot.dispose(()=>{
+              if (typeof document !== 'undefined') {
+                  document.querySelectorAll('style[data-file="${cwd}/dir/mockFilename.css.ts"]').forEach((style)=>{
+                      style.textContent = '';
+                  });
+              }
+          });
+      }
+      "
+    `);
+  });
+
+  it("should propagate hot updates of style modules to their importers", () => {
+    const transformStyles = (source: string) =>
+      transform(source, "commonjs", {}, "dir/styles.css.ts", { hmr: "webpack" });
+
+    // Like vanilla-extract, class names depend on the position of the style in its file scope
+    let position = 0;
+    const vanillaExtract = {
+      style: (_rule: unknown, debugId: string) => `${debugId}_${position++}`,
+    };
+    const fileScope = {
+      setFileScope: () => {
+        position = 0;
+      },
+      endFileScope: () => {},
+    };
+
+    // Minimal webpack-like runtime, an update re-runs modules up to the ones accepting themselves
+    type HotModule = {
+      code: string;
+      exports: Record<string, unknown>;
+      parents: string[];
+      isSelfAccepted: boolean;
+      disposeHandlers: (() => void)[];
+    };
+    const modules: Record<string, HotModule> = {};
+    const run = (id: string, code: string) => {
+      const module: HotModule = {
+        code,
+        exports: {},
+        parents: modules[id]?.parents ?? [],
+        isSelfAccepted: false,
+        disposeHandlers: [],
+      };
+      modules[id] = module;
+
+      const hot = {
+        accept: (dependencies?: unknown) => {
+          if (dependencies === undefined || typeof dependencies === "function") {
+            module.isSelfAccepted = true;
+          }
+        },
+        dispose: (handler: () => void) => {
+          module.disposeHandlers.push(handler);
+        },
+      };
+      const require = (request: string) => {
+        switch (request) {
+          case "@vanilla-extract/css":
+            return vanillaExtract;
+          case "@vanilla-extract/css/fileScope":
+            return fileScope;
+          default:
+            if (!modules[request].parents.includes(id)) {
+              modules[request].parents.push(id);
+            }
+            return modules[request].exports;
+        }
+      };
+      new Function("require", "module", "exports", code)(
+        require,
+        { hot, exports: module.exports },
+        module.exports
+      );
+    };
+    const update = (id: string, code: string) => {
+      const outdated: string[] = [];
+      const queue = [id];
+      while (queue.length > 0) {
+        const outdatedId = queue.shift()!;
+        if (outdated.includes(outdatedId)) {
+          continue;
+        }
+        outdated.push(outdatedId);
+
+        const { isSelfAccepted, parents } = modules[outdatedId];
+        if (!isSelfAccepted) {
+          if (parents.length === 0) {
+            throw new Error(`${outdatedId} needs a full reload`);
+          }
+          queue.push(...parents);
+        }
+      }
+
+      outdated.forEach((outdatedId) =>
+        modules[outdatedId].disposeHandlers.forEach((handler) => handler())
+      );
+      outdated.forEach((outdatedId) =>
+        run(outdatedId, outdatedId === id ? code : modules[outdatedId].code)
+      );
+    };
+
+    run(
+      "./styles.css",
+      transformStyles(`
+        import { style } from '@vanilla-extract/css';
+
+        export const root = style({ zIndex: 1 });
+      `)
+    );
+    // Components accept their own updates, like with React Fast Refresh
+    run(
+      "./component",
+      `
+        const styles = require("./styles.css");
+        exports.className = styles.root;
+        module.hot.accept();
+      `
+    );
+    expect(modules["./component"].exports.className).toBe("root_0");
+
+    update(
+      "./styles.css",
+      transformStyles(`
+        import { style } from '@vanilla-extract/css';
+
+        export const header = style({ zIndex: 2 });
+
+        export const root = style({ zIndex: 1 });
+      `)
+    );
+    expect(modules["./component"].exports.className).toBe("root_1");
+  });
 });
+
+// The report is only exposed by the custom transform binding
+(process.env.SWC_TRANSFORM_CUSTOM === "1" ? describe : describe.skip)(
+  "style module report",
+  () => {
+    it("should report style calls of the file", () => {
+      const { transformWithReportSync } = require("../index");
+      const source = `
+      import { style, recipe, globalStyle } from '@vanilla-extract/css';
+
+      const base = style({ zIndex: 1 });
+
+      export const button = { root: style({ zIndex: 2 }, 'buttonRoot') };
+
+      globalStyle('html', { zIndex: 3 });
+
+      export default recipe({});
+
+      export { base as baseClass };
+    `;
+
+      const { report } = transformWithReportSync(
+        source,
+        true,
+        Buffer.from(
+          JSON.stringify({
+            filename: "dir/mockFilename.css.ts",
+            jsc: { parser: { syntax: "ecmascript" }, target: "es2022" },
+          })
+        ),
+        Buffer.from(JSON.stringify({}))
+      );
+
+      expect(JSON.parse(report)).toMatchObject({
+        filePath: `${cwd}/dir/mockFilename.css.ts`,
+        packageName: "swc-plugin-vanilla-extract",
+        isTransformed: true,
+        styles: [
+          {
+            kind: "style",
+            isWrapper: false,
+            localName: "base",
+            exportName: "baseClass",
+            debugId: "base",
+            start: { line: 4 },
+          },
+          {
+            kind: "style",
+            isWrapper: false,
+            localName: "button",
+            exportName: "button",
+            debugId: "buttonRoot",
+            start: { line: 6 },
+          },
+          {
+            kind: "globalStyle",
+            isWrapper: false,
+            localName: null,
+            exportName: null,
+            debugId: null,
+            start: { line: 8 },
+          },
+          {
+            kind: "recipe",
+            isWrapper: false,
+            localName: null,
+            exportName: "default",
+            debugId: "mockFilename",
+            start: { line: 10 },
+          },
+        ],
+      });
+    });
+
+    it("should report fixes with their applicability", () => {
+      const { transformWithReportSync } = require("../index");
+      const source = `
+      import { style } from '@vanilla-extract/css';
+
+      export const root = [style({ colr: 'red' }), style({})];
+    `;
+
+      const { diagnostics } = transformWithReportSync(
+        source,
+        true,
+        Buffer.from(
+          JSON.stringify({
+            filename: "dir/mockFilename.css.ts",
+            jsc: { parser: { syntax: "ecmascript" }, target: "es2022" },
+          })
+        ),
+        Buffer.from(JSON.stringify({ duplicateDebugIds: "warning" }))
+      );
+
+      expect(JSON.parse(diagnostics)).toMatchObject([
+        {
+          code: "VE004",
+          fix: {
+            message: "Replace with `color`",
+            applicability: "maybeIncorrect",
+            edits: [{ replacement: "color" }],
+          },
+        },
+        {
+          code: "VE009",
+          fix: {
+            message: "Pass `root_2` as the debug id",
+            applicability: "machineApplicable",
+          },
+        },
+      ]);
+    });
+  }
+);
@@ -12,19 +12,21 @@ use std::{env, panic::set_hook, sync::Arc};
 
 use backtrace::Backtrace;
 
+use serde_json::{json, Value};
 use swc_core::{
     base::{config::Options, Compiler, TransformOutput},
     common::{
         comments::{Comments, SingleThreadedComments},
+        pass::Either,
         sync::Lazy,
-        FileName, FilePathMapping, SourceMap,
-    },
-    ecma::{
-        transforms::base::pass::noop,
-        visit::{as_folder, Fold},
+        BytePos, FileName, FilePathMapping, SourceMap,
     },
+    ecma::{transforms::base::pass::noop, visit::Fold},
+};
+use swc_vanilla_extract_visitor::{
+    Config, Diagnostic, DiagnosticLog, StyleModuleReport, StyleModuleReportSlot,
+    VanillaExtractBuilder,
 };
-use swc_vanilla_extract_visitor::create_extract_visitor;
 
 use std::path::Path;
 
@@ -75,12 +77,67 @@ pub fn transform_sync(
     s: String,
     _is_module: bool,
     opts: Buffer,
-    _instrument_opts: Buffer,
+    instrument_opts: Buffer,
+) -> napi::Result<TransformOutput> {
+    transform(s, opts, instrument_opts, None, None)
+}
+
+#[napi(object)]
+pub struct TransformWithReportOutput {
+    pub code: String,
+    pub map: Option<String>,
+    /// `StyleModuleReport` serialized to JSON, if the file is a style file.
+    pub report: Option<String>,
+    /// Warnings reported while transforming the file serialized to JSON, errors fail the transform.
+    pub diagnostics: String,
+}
+
+#[napi]
+pub fn transform_with_report_sync(
+    s: String,
+    _is_module: bool,
+    opts: Buffer,
+    instrument_opts: Buffer,
+) -> napi::Result<TransformWithReportOutput> {
+    let report = StyleModuleReportSlot::new();
+    let diagnostics = DiagnosticLog::new();
+    let output = transform(
+        s,
+        opts,
+        instrument_opts,
+        Some(report.clone()),
+        Some(diagnostics.clone()),
+    )?;
+
+    let cm = get_compiler().cm.clone();
+    Ok(TransformWithReportOutput {
+        code: output.code,
+        map: output.map,
+        report: report
+            .take()
+            .map(|report| serialize_report(&cm, &report).to_string()),
+        diagnostics: Value::Array(
+            diagnostics
+                .take()
+                .iter()
+                .map(|diagnostic| serialize_diagnostic(&cm, diagnostic))
+                .collect(),
+        )
+        .to_string(),
+    })
+}
+
+fn transform(
+    s: String,
+    opts: Buffer,
+    instrument_opts: Buffer,
+    report: Option<StyleModuleReportSlot>,
+    diagnostics: Option<DiagnosticLog>,
 ) -> napi::Result<TransformOutput> {
     let c = get_compiler();
 
     let mut options: Options = get_deserialized(&opts)?;
-    let instrument_option = "dummy".to_string();
+    let instrument_option: Value = get_deserialized(&instrument_opts)?;
 
     if !options.filename.is_empty() {
         options.config.adjust(Path::new(&options.filename));
@@ -98,18 +155,21 @@ pub fn transform_sync(
                 };
 
                 let fm = c.cm.new_source_file(filename.clone(), s);
+                let comments = SingleThreadedComments::default();
                 c.process_js_with_custom_pass(
                     fm,
                     None,
                     handler,
                     &options,
-                    Default::default(),
+                    comments.clone(),
                     |_program| {
                         vanilla_extract(
                             c.cm.clone(),
-                            SingleThreadedComments::default(),
+                            comments,
                             instrument_option,
                             filename.to_string(),
+                            report,
+                            diagnostics,
                         )
                     },
                     |_| noop(),
@@ -120,6 +180,88 @@ pub fn transform_sync(
     .convert_err()
 }
 
+fn serialize_report(cm: &SourceMap, report: &StyleModuleReport) -> Value {
+    let styles = report
+        .styles
+        .iter()
+        .map(|style| {
+            json!({
+                "kind": style.kind,
+                "isWrapper": style.is_wrapper,
+                "localName": style.local_name,
+                "exportName": style.export_name,
+                "debugId": style.debug_id,
+                "start": serialize_position(cm, style.span.lo),
+                "end": serialize_position(cm, style.span.hi),
+            })
+        })
+        .collect::<Vec<_>>();
+
+    json!({
+        "filePath": report.file_path,
+        "packageName": report.package_name,
+        "isTransformed": report.is_transformed,
+        "styles": styles,
+    })
+}
+
+fn serialize_position(cm: &SourceMap, pos: BytePos) -> Value {
+    let loc = cm.lookup_char_pos(pos);
+
+    json!({ "line": loc.line, "column": loc.col_display })
+}
+
+fn serialize_diagnostic(cm: &SourceMap, diagnostic: &Diagnostic) -> Value {
+    let notes = diagnostic
+        .notes
+        .iter()
+        .map(|(span, message)| {
+            json!({
+                "message": message,
+                "start": serialize_position(cm, span.lo),
+                "end": serialize_position(cm, span.hi),
+            })
+        })
+        .collect::<Vec<_>>();
+    // Edits carry byte offsets within the file along with positions, to apply them to the source
+    // without parsing it again
+    let fix = diagnostic.fix.as_ref().map(|fix| {
+        let edits = fix
+            .edits
+            .iter()
+            .map(|edit| {
+                json!({
+                    "start": serialize_position(cm, edit.span.lo),
+                    "end": serialize_position(cm, edit.span.hi),
+                    "range": [
+                        cm.lookup_byte_offset(edit.span.lo).pos.0,
+                        cm.lookup_byte_offset(edit.span.hi).pos.0,
+                    ],
+                    "replacement": edit.replacement,
+                })
+            })
+            .collect::<Vec<_>>();
+
+        json!({
+            "message": fix.message,
+            "applicability": fix.applicability.to_string(),
+            "edits": edits,
+        })
+    });
+
+    json!({
+        "code": diagnostic.rule.code(),
+        "rule": diagnostic.rule.name(),
+        "severity": diagnostic.severity.to_string(),
+        "message": diagnostic.message,
+        "help": diagnostic.rule.help(),
+        "start": serialize_position(cm, diagnostic.span.lo),
+        "end": serialize_position(cm, diagnostic.span.hi),
+        "notes": notes,
+        "fix": fix,
+    })
+}
+
 fn vanilla_extract<
     'a,
     C: Comments + 'a + std::clone::Clone,
@@ -127,14 +269,17 @@ fn vanilla_extract<
 >(
     source_map: Arc<S>,
     comments: C,
-    _instrument_options: String,
+    instrument_options: Value,
     filename: String,
+    report: Option<StyleModuleReportSlot>,
+    diagnostics: Option<DiagnosticLog>,
 ) -> impl Fold + 'a {
-    let visitor = create_extract_visitor(
-        source_map,
-        comments,
-        &filename,
-        "swc-plugin-vanilla-extract",
+    let config = match Config::from_plugin_options(Some(&instrument_options.to_string())) {
+        Some(config) => config,
+        None => return Either::Right(noop()),
+    };
+
+    let mut builder = VanillaExtractBuilder::from(config).package_dir(
         std::env::current_dir()
             .expect("Should exist")
             .as_os_str()
@@ -142,5 +287,13 @@ fn vanilla_extract<
             .expect("Should exist"),
     );
 
-    as_folder(visitor)
+    if let Some(report) = report {
+        builder = builder.report(report);
+    }
+
+    if let Some(diagnostics) = diagnostics {
+        builder = builder.diagnostics(diagnostics);
+    }
+
+    Either::Left(builder.build(source_map, comments, &filename))
 }